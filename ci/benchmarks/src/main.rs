@@ -30,3 +30,21 @@ fn alloc_ratio_u64_bench(b: &mut Bencher) {
         Ratio::new(a, b)
     });
 }
+
+fn harmonic_terms(n: u64) -> Vec<BigRational> {
+    (1..=n)
+        .map(|i| BigRational::new(BigInt::from(1), BigInt::from(i)))
+        .collect()
+}
+
+#[bench]
+fn sum_fold_bigint_bench(b: &mut Bencher) {
+    let terms = harmonic_terms(1000);
+    b.iter(|| terms.iter().sum::<BigRational>());
+}
+
+#[bench]
+fn sum_pairwise_bigint_bench(b: &mut Bencher) {
+    let terms = harmonic_terms(1000);
+    b.iter(|| Ratio::sum_pairwise(&terms));
+}
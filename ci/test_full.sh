@@ -27,8 +27,8 @@ if ! check_version $MSRV ; then
   exit 1
 fi
 
-STD_FEATURES=(num-bigint-std serde)
-NO_STD_FEATURES=(num-bigint serde)
+STD_FEATURES=(num-bigint-std serde subtle libm)
+NO_STD_FEATURES=(num-bigint serde subtle libm)
 echo "Testing supported features: ${STD_FEATURES[*]}"
 echo " no_std supported features: ${NO_STD_FEATURES[*]}"
 
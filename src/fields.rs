@@ -0,0 +1,134 @@
+use crate::{Ratio, ZeroDenominator};
+
+use core::fmt;
+use core::marker::PhantomData;
+use num_integer::Integer;
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+
+/// A wrapper that serializes a `Ratio` as a struct with named `numer` and `denom` fields,
+/// rather than the tuple `(numer, denom)` that `Ratio` itself uses.
+///
+/// This reads better in self-describing formats, e.g. `{ numer: 3, denom: 4 }` in YAML.
+/// Deserializing rejects a zero denominator, and otherwise keeps the parts as written.
+#[derive(Copy, Clone, Debug)]
+pub struct RatioFields<T>(pub Ratio<T>);
+
+impl<T> From<Ratio<T>> for RatioFields<T> {
+    fn from(ratio: Ratio<T>) -> Self {
+        RatioFields(ratio)
+    }
+}
+
+impl<T> From<RatioFields<T>> for Ratio<T> {
+    fn from(fields: RatioFields<T>) -> Self {
+        fields.0
+    }
+}
+
+const FIELDS: &[&str] = &["numer", "denom"];
+
+impl<T: serde::Serialize> serde::Serialize for RatioFields<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_struct("Ratio", 2)?;
+        state.serialize_field("numer", self.0.numer())?;
+        state.serialize_field("denom", self.0.denom())?;
+        state.end()
+    }
+}
+
+impl<'de, T> serde::Deserialize<'de> for RatioFields<T>
+where
+    T: serde::Deserialize<'de> + Clone + Integer,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("Ratio", FIELDS, FieldsVisitor(PhantomData))
+    }
+}
+
+enum Field {
+    Numer,
+    Denom,
+}
+
+impl<'de> serde::Deserialize<'de> for Field {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct FieldVisitor;
+
+        impl<'de> Visitor<'de> for FieldVisitor {
+            type Value = Field;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("`numer` or `denom`")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Field, E> {
+                match value {
+                    "numer" => Ok(Field::Numer),
+                    "denom" => Ok(Field::Denom),
+                    _ => Err(de::Error::unknown_field(value, FIELDS)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(FieldVisitor)
+    }
+}
+
+struct FieldsVisitor<T>(PhantomData<T>);
+
+impl<T: Integer> FieldsVisitor<T> {
+    fn finish<E: de::Error>(numer: T, denom: T) -> Result<RatioFields<T>, E> {
+        if denom.is_zero() {
+            Err(de::Error::custom(ZeroDenominator))
+        } else {
+            Ok(RatioFields(Ratio::new_raw(numer, denom)))
+        }
+    }
+}
+
+impl<'de, T> Visitor<'de> for FieldsVisitor<T>
+where
+    T: serde::Deserialize<'de> + Clone + Integer,
+{
+    type Value = RatioFields<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a ratio with `numer` and `denom` fields")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let numer = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let denom = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        Self::finish(numer, denom)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut numer = None;
+        let mut denom = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                Field::Numer if numer.is_some() => return Err(de::Error::duplicate_field("numer")),
+                Field::Denom if denom.is_some() => return Err(de::Error::duplicate_field("denom")),
+                Field::Numer => numer = Some(map.next_value()?),
+                Field::Denom => denom = Some(map.next_value()?),
+            }
+        }
+        let numer = numer.ok_or_else(|| de::Error::missing_field("numer"))?;
+        let denom = denom.ok_or_else(|| de::Error::missing_field("denom"))?;
+        Self::finish(numer, denom)
+    }
+}
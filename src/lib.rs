@@ -39,11 +39,18 @@ use num_bigint::{BigInt, BigUint, Sign, ToBigInt};
 use num_integer::Integer;
 use num_traits::float::FloatCore;
 use num_traits::{
-    Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, ConstOne, ConstZero, FromPrimitive,
-    Inv, Num, NumCast, One, Pow, Signed, ToPrimitive, Unsigned, Zero,
+    AsPrimitive, Bounded, CheckedAdd, CheckedDiv, CheckedEuclid, CheckedMul, CheckedNeg,
+    CheckedSub, ConstOne, ConstZero, Euclid, FromPrimitive, Inv, Num, NumCast, One, Pow, Signed,
+    ToPrimitive, Unsigned, WrappingAdd, WrappingMul, Zero,
 };
 
+#[cfg(feature = "serde")]
+mod fields;
 mod pow;
+#[cfg(feature = "serde")]
+pub use crate::fields::RatioFields;
+pub use crate::iter_sum_product::CheckedSumExt;
+pub use crate::pow::CheckedPow;
 
 /// Represents the ratio between two numbers.
 #[derive(Copy, Clone, Debug)]
@@ -98,6 +105,12 @@ impl<T> Ratio<T> {
     pub const fn denom(&self) -> &T {
         &self.denom
     }
+
+    /// Gets immutable references to the numerator and denominator.
+    #[inline]
+    pub const fn parts(&self) -> (&T, &T) {
+        (&self.numer, &self.denom)
+    }
 }
 
 impl<T: Clone + Integer> Ratio<T> {
@@ -111,6 +124,96 @@ impl<T: Clone + Integer> Ratio<T> {
         ret
     }
 
+    /// Creates a new `Ratio`, reduced like `new`, or returns `ZeroDenominator` if `denom` is
+    /// zero.
+    #[inline]
+    pub fn checked(numer: T, denom: T) -> Result<Ratio<T>, ZeroDenominator> {
+        if denom.is_zero() {
+            Err(ZeroDenominator)
+        } else {
+            Ok(Ratio::new(numer, denom))
+        }
+    }
+
+    /// Creates a new `Ratio`, only reducing it if `reduce` is true.
+    ///
+    /// Callers that know `numer` and `denom` are already coprime, with a positive `denom`, can
+    /// skip the reduction while still being protected from a zero denominator.
+    ///
+    /// **Panics if `denom` is zero.**
+    #[inline]
+    pub fn new_with_reduce(numer: T, denom: T, reduce: bool) -> Ratio<T> {
+        if reduce {
+            Ratio::new(numer, denom)
+        } else if denom.is_zero() {
+            panic!("denominator == 0");
+        } else {
+            Ratio::new_raw(numer, denom)
+        }
+    }
+
+    /// Creates a `Ratio` without reducing, like `new_raw`, but debug builds assert that `denom`
+    /// is non-zero.
+    ///
+    /// This catches a zero denominator where it is introduced, rather than in a later
+    /// comparison or operation, at no cost in release builds. `new_raw` itself can't check,
+    /// since it is a `const fn` without any bounds on `T`, so it stays the unchecked
+    /// constructor, also for deliberate `±∞` sentinels.
+    #[inline]
+    pub fn new_raw_debug_checked(numer: T, denom: T) -> Ratio<T> {
+        debug_assert!(!denom.is_zero(), "denominator == 0");
+        Ratio::new_raw(numer, denom)
+    }
+
+    /// Creates a `Ratio` from a `(numer, denom)` pair without reducing, like
+    /// `new_raw_debug_checked`.
+    ///
+    /// `From<(T, T)>` reduces the pair like `new` instead, and panics on a zero denominator in
+    /// all builds.
+    #[inline]
+    pub fn from_pair_raw(pair: (T, T)) -> Ratio<T> {
+        Ratio::new_raw_debug_checked(pair.0, pair.1)
+    }
+
+    /// Creates a `Ratio` representing `mantissa * 10^exp10`, reduced.
+    ///
+    /// Trailing zeros of `mantissa` are cancelled against a negative exponent first, but this
+    /// may still overflow fixed-size types; see `checked_from_scientific_parts`.
+    pub fn from_scientific_parts(mut mantissa: T, mut exp10: i32) -> Ratio<T> {
+        if mantissa.is_zero() {
+            return Ratio::zero();
+        }
+        let ten = ten::<T>();
+        while exp10 < 0 && mantissa.is_multiple_of(&ten) {
+            mantissa = mantissa / ten.clone();
+            exp10 += 1;
+        }
+        let scale = num_traits::pow(ten, exp10.unsigned_abs() as usize);
+        if exp10 < 0 {
+            Ratio::new(mantissa, scale)
+        } else {
+            Ratio::new(mantissa * scale, One::one())
+        }
+    }
+
+    /// Returns `self * 10^scale` rounded to the nearest integer, with half-way cases rounded
+    /// away from zero.
+    ///
+    /// This is the value stored for a fixed-point decimal with `scale` fractional digits, so
+    /// `1/3` at scale 4 is `3333`. The scaling is exact, but it may overflow fixed-size types;
+    /// see `checked_to_scaled_integer`.
+    pub fn to_scaled_integer(&self, scale: u32) -> T {
+        let scaled = self * num_traits::pow(ten::<T>(), scale as usize);
+        scaled.round().to_integer()
+    }
+
+    /// Creates a `Ratio` representing the fixed-point decimal `value / 10^scale`, reduced.
+    ///
+    /// This is the inverse of `to_scaled_integer`, for values that were stored exactly.
+    pub fn from_scaled_integer(value: T, scale: u32) -> Ratio<T> {
+        Ratio::new(value, num_traits::pow(ten::<T>(), scale as usize))
+    }
+
     /// Creates a `Ratio` representing the integer `t`.
     #[inline]
     pub fn from_integer(t: T) -> Ratio<T> {
@@ -129,10 +232,131 @@ impl<T: Clone + Integer> Ratio<T> {
         self.denom.is_one()
     }
 
+    /// Returns true if the reduced denominator is a power of two, so the value is exactly
+    /// representable in binary fixed-point.
+    ///
+    /// Integers are dyadic with a denominator of `2^0`; a zero denominator is not.
+    pub fn is_dyadic(&self) -> bool {
+        self.terminates_in_base(T::one() + T::one())
+    }
+
+    /// Returns true if the expansion of `self` in the given `base` terminates, that is if every
+    /// prime factor of the reduced denominator divides `base`.
+    ///
+    /// Integers terminate in every base; a zero denominator never does.
+    ///
+    /// **Panics if `base` is less than two.**
+    pub fn terminates_in_base(&self, base: T) -> bool {
+        assert!(base > T::one(), "base < 2");
+        if self.denom.is_zero() {
+            return false;
+        }
+        // Like `reduce`, equal parts are one, which also keeps the gcd of two `T::MIN` parts from
+        // overflowing. The sign is left alone, since a `T::MIN` denominator can't be negated.
+        if self.numer.is_zero() || self.numer == self.denom {
+            return true;
+        }
+        let mut denom = self.denom.clone() / self.numer.gcd(&self.denom);
+        let mut g = denom.gcd(&base);
+        while !g.is_one() {
+            denom = denom / g.clone();
+            g = denom.gcd(&g);
+        }
+        denom.is_one() || (denom < T::zero() && (T::zero() - denom).is_one())
+    }
+
+    /// Rounds to the nearest multiple of `2^-frac_bits`, with half-way cases rounded to an even
+    /// multiple, as for conversion to a fixed-point format with `frac_bits` fractional bits.
+    ///
+    /// The result is reduced, so its denominator may be a smaller power of two.
+    ///
+    /// **Panics if `denom` is zero.**
+    pub fn to_dyadic(&self, frac_bits: u32) -> Ratio<T> {
+        let two = T::one() + T::one();
+        let scale = num_traits::pow(two, frac_bits as usize);
+        let reduced = self.reduced();
+        let (quot, rem) = (reduced.numer * scale.clone()).div_mod_floor(&reduced.denom);
+        let quot = match rem.cmp(&(reduced.denom - rem.clone())) {
+            cmp::Ordering::Less => quot,
+            cmp::Ordering::Greater => quot + T::one(),
+            cmp::Ordering::Equal if quot.is_odd() => quot + T::one(),
+            cmp::Ordering::Equal => quot,
+        };
+        Ratio::new(quot, scale)
+    }
+
+    /// Returns `self * 2^frac_bits` rounded to the nearest integer, with half-way cases rounded
+    /// to even, or `None` if that overflows. This is the value stored in a `Qm.n` fixed-point
+    /// format with `n = frac_bits`, and matches the numerator of `to_dyadic`.
+    ///
+    /// The bits are produced one at a time by long division, so neither `2^frac_bits` nor the
+    /// scaled numerator have to fit, only the result. For example, `1/3` in Q0.7 is `43i8`.
+    ///
+    /// **Panics if `denom` is zero.**
+    pub fn to_q_format(&self, frac_bits: u32) -> Option<T>
+    where
+        T: CheckedAdd + CheckedSub,
+    {
+        let reduced = self.reduced();
+        let negative = reduced.numer < T::zero();
+        let (mut quot, rem) = reduced.numer.div_rem(&reduced.denom);
+        let mut rem = if negative { T::zero() - rem } else { rem };
+        // Steps `quot` one unit away from zero.
+        let step = |quot: T| {
+            if negative {
+                quot.checked_sub(&T::one())
+            } else {
+                quot.checked_add(&T::one())
+            }
+        };
+
+        for _ in 0..frac_bits {
+            if quot.is_zero() && rem.is_zero() {
+                break;
+            }
+            quot = quot.checked_add(&quot)?;
+            // Double the remainder without overflowing, carrying a bit if it reaches `denom`.
+            let rest = reduced.denom.clone() - rem.clone();
+            if rem >= rest {
+                quot = step(quot)?;
+                rem = rem - rest;
+            } else {
+                rem = rem.clone() + rem;
+            }
+        }
+
+        let rest = reduced.denom - rem.clone();
+        match rem.cmp(&rest) {
+            cmp::Ordering::Less => Some(quot),
+            cmp::Ordering::Equal if quot.is_even() => Some(quot),
+            _ => step(quot),
+        }
+    }
+
+    /// Creates a `Ratio` representing the `Qm.n` fixed-point value `value / 2^frac_bits`,
+    /// reduced.
+    ///
+    /// This is the inverse of `to_q_format`. Factors of two in `value` are cancelled first,
+    /// so `2^frac_bits` only has to fit in `T` if `value` is odd.
+    pub fn from_q_format(mut value: T, mut frac_bits: u32) -> Ratio<T> {
+        if value.is_zero() {
+            return Ratio::zero();
+        }
+        let two = T::one() + T::one();
+        while frac_bits > 0 && value.is_even() {
+            value = value / two.clone();
+            frac_bits -= 1;
+        }
+        Ratio::new_raw(value, num_traits::pow(two, frac_bits as usize))
+    }
+
     /// Puts self into lowest terms, with `denom` > 0.
     ///
+    /// This normalizes a value built with `new_raw` in place, without the clone that `reduced`
+    /// makes.
+    ///
     /// **Panics if `denom` is zero.**
-    fn reduce(&mut self) {
+    pub fn reduce(&mut self) {
         if self.denom.is_zero() {
             panic!("denominator == 0");
         }
@@ -144,28 +368,95 @@ impl<T: Clone + Integer> Ratio<T> {
             self.set_one();
             return;
         }
-        let g: T = self.numer.gcd(&self.denom);
+        // A unit denominator is already canonical
+        if self.denom.is_one() {
+            return;
+        }
+
+        // A unit numerator is coprime to anything, so only the sign may need fixing
+        if !self.numer.is_one() {
+            let g: T = self.numer.gcd(&self.denom);
+
+            // self.numer /= g;
+            replace_with(&mut self.numer, |x| x / g.clone());
+
+            // self.denom /= g;
+            replace_with(&mut self.denom, |x| x / g);
+        }
 
-        // FIXME(#5992): assignment operator overloads
-        // T: Clone + Integer != T: Clone + NumAssign
+        // keep denom positive!
+        if self.denom < T::zero() {
+            replace_with(&mut self.numer, |x| T::zero() - x);
+            replace_with(&mut self.denom, |x| T::zero() - x);
+        }
+    }
 
-        #[inline]
-        fn replace_with<T: Zero>(x: &mut T, f: impl FnOnce(T) -> T) {
-            let y = core::mem::replace(x, T::zero());
-            *x = f(y);
+    /// Puts self into lowest terms like `reduce`, and returns the gcd that was divided out of
+    /// the numerator and denominator.
+    ///
+    /// The gcd is never negative, so making `denom` positive is not folded in: if `denom` was
+    /// negative, both parts also changed sign. For a zero numerator, the gcd is `|denom|`.
+    ///
+    /// **Panics if `denom` is zero.**
+    pub fn reduce_get_gcd(&mut self) -> T {
+        if self.denom.is_zero() {
+            panic!("denominator == 0");
+        }
+        let g: T = self.numer.gcd(&self.denom);
+        if !g.is_one() {
+            replace_with(&mut self.numer, |x| x / g.clone());
+            replace_with(&mut self.denom, |x| x / g.clone());
+        }
+        if self.denom < T::zero() {
+            replace_with(&mut self.numer, |x| T::zero() - x);
+            replace_with(&mut self.denom, |x| T::zero() - x);
         }
+        g
+    }
+
+    /// Returns true if `self` and `other` are equal and both are in lowest terms with a positive
+    /// denominator, i.e. in the canonical form that `new` produces.
+    ///
+    /// Unlike `==`, this distinguishes `new_raw(2, 4)` from `new(1, 2)`, which is useful for
+    /// asserting that values have been reduced.
+    pub fn strict_eq(&self, other: &Ratio<T>) -> bool {
+        self.is_reduced()
+            && other.is_reduced()
+            && self.numer == other.numer
+            && self.denom == other.denom
+    }
+
+    fn is_reduced(&self) -> bool {
+        self.denom > T::zero() && self.numer.gcd(&self.denom).is_one()
+    }
 
-        // self.numer /= g;
-        replace_with(&mut self.numer, |x| x / g.clone());
+    /// Puts self into lowest terms, with `denom` > 0, returning true if that changed its
+    /// representation.
+    ///
+    /// **Panics if `denom` is zero.**
+    pub fn try_reduce(&mut self) -> bool {
+        if self.denom.is_zero() {
+            panic!("denominator == 0");
+        }
+        if self.numer == self.denom {
+            let changed = !self.denom.is_one();
+            self.set_one();
+            return changed;
+        }
 
-        // self.denom /= g;
-        replace_with(&mut self.denom, |x| x / g);
+        let g: T = self.numer.gcd(&self.denom);
+        let changed = !g.is_one() || self.denom < T::zero();
+        if !g.is_one() {
+            replace_with(&mut self.numer, |x| x / g.clone());
+            replace_with(&mut self.denom, |x| x / g);
+        }
 
         // keep denom positive!
         if self.denom < T::zero() {
             replace_with(&mut self.numer, |x| T::zero() - x);
             replace_with(&mut self.denom, |x| T::zero() - x);
         }
+        changed
     }
 
     /// Returns a reduced copy of self.
@@ -271,6 +562,22 @@ impl<T: Clone + Integer> Ratio<T> {
         Ratio::new_raw(self.numer.clone() % self.denom.clone(), self.denom.clone())
     }
 
+    /// Splits into the parts of a mixed number, `(whole, fraction)`, with the whole part rounded
+    /// towards zero and the fraction reduced.
+    ///
+    /// The whole part carries the sign and the fraction is its non-negative remainder, so
+    /// `-7/3` splits into `(-2, 1/3)`. When the whole part is zero, the fraction keeps the sign
+    /// instead, so `-1/3` splits into `(0, -1/3)`.
+    pub fn as_mixed(&self) -> (T, Ratio<T>) {
+        let (whole, rem) = self.numer.div_rem(&self.denom);
+        let fraction = Ratio::new(rem, self.denom.clone());
+        if !whole.is_zero() && fraction < Ratio::zero() {
+            (whole, Ratio::zero() - fraction)
+        } else {
+            (whole, fraction)
+        }
+    }
+
     /// Raises the `Ratio` to the power of an exponent.
     #[inline]
     pub fn pow(&self, expon: i32) -> Ratio<T>
@@ -279,1575 +586,4696 @@ impl<T: Clone + Integer> Ratio<T> {
     {
         Pow::pow(self, expon)
     }
-}
 
-#[cfg(feature = "num-bigint")]
-impl Ratio<BigInt> {
-    /// Converts a float into a rational number.
-    pub fn from_float<T: FloatCore>(f: T) -> Option<BigRational> {
-        if !f.is_finite() {
-            return None;
-        }
-        let (mantissa, exponent, sign) = f.integer_decode();
-        let bigint_sign = if sign == 1 { Sign::Plus } else { Sign::Minus };
-        if exponent < 0 {
-            let one: BigInt = One::one();
-            let denom: BigInt = one << ((-exponent) as usize);
-            let numer: BigUint = FromPrimitive::from_u64(mantissa).unwrap();
-            Some(Ratio::new(BigInt::from_biguint(bigint_sign, numer), denom))
+    /// Returns true if `self` and `other` differ by no more than `tol`.
+    ///
+    /// The difference is always taken as the larger minus the smaller value, so this also works
+    /// for unsigned element types. For values on opposite sides of zero, whose difference may
+    /// not fit, each is compared against the tolerance separately instead.
+    pub fn is_close(&self, other: &Ratio<T>, tol: &Ratio<T>) -> bool {
+        let (lo, hi) = if self <= other {
+            (self, other)
         } else {
-            let mut numer: BigUint = FromPrimitive::from_u64(mantissa).unwrap();
-            numer <<= exponent as usize;
-            Some(Ratio::from_integer(BigInt::from_biguint(
-                bigint_sign,
-                numer,
-            )))
+            (other, self)
+        };
+        let zero = Ratio::zero();
+        if *lo < zero && *hi > zero {
+            // hi - lo <= tol exactly when lo >= -(tol - hi), where tol - hi is in 0..=tol.
+            if hi > tol {
+                return false;
+            }
+            return *lo >= zero.clone() - (tol - hi);
         }
+        hi - lo <= *tol
     }
-}
 
-impl<T: Clone + Integer> Default for Ratio<T> {
-    /// Returns zero
-    fn default() -> Self {
-        Ratio::zero()
+    /// Compares `self` to zero, using only the signs of the numerator and denominator.
+    ///
+    /// This is equivalent to `self.cmp(&Ratio::zero())`, without constructing a zero ratio.
+    #[inline]
+    pub fn cmp_zero(&self) -> cmp::Ordering {
+        let zero = T::zero();
+        let ord = self.numer.cmp(&zero);
+        if self.denom < zero {
+            ord.reverse()
+        } else {
+            ord
+        }
     }
-}
 
-// From integer
-impl<T> From<T> for Ratio<T>
-where
-    T: Clone + Integer,
-{
-    fn from(x: T) -> Ratio<T> {
-        Ratio::from_integer(x)
-    }
-}
+    /// Compares the magnitudes `|self|` and `|other|`, without computing either absolute value.
+    ///
+    /// With mixed signs, the positive value is negated and compared against the negative one,
+    /// so this also works for a two's-complement `MIN` whose magnitude isn't representable.
+    pub fn abs_cmp(&self, other: &Ratio<T>) -> cmp::Ordering {
+        use cmp::Ordering::*;
+
+        // Negates a positive ratio, preferring to negate a positive numerator.
+        fn flip<T: Clone + Integer>(r: &Ratio<T>) -> Ratio<T> {
+            if r.numer > T::zero() {
+                Ratio::new_raw(T::zero() - r.numer.clone(), r.denom.clone())
+            } else {
+                Ratio::new_raw(r.numer.clone(), T::zero() - r.denom.clone())
+            }
+        }
 
-// From pair (through the `new` constructor)
-impl<T> From<(T, T)> for Ratio<T>
-where
-    T: Clone + Integer,
-{
-    fn from(pair: (T, T)) -> Ratio<T> {
-        Ratio::new(pair.0, pair.1)
+        match (self.cmp_zero(), other.cmp_zero()) {
+            (Equal, Equal) => Equal,
+            (Equal, _) => Less,
+            (_, Equal) => Greater,
+            (Greater, Greater) => self.cmp(other),
+            (Less, Less) => other.cmp(self),
+            (Greater, Less) => flip(self).cmp(other).reverse(),
+            (Less, Greater) => self.cmp(&flip(other)).reverse(),
+        }
     }
-}
-
-// Comparisons
 
-// Mathematically, comparing a/b and c/d is the same as comparing a*d and b*c, but it's very easy
-// for those multiplications to overflow fixed-size integers, so we need to take care.
+    /// Returns `true` if `self` lies between `lo` and `hi`, including the endpoints if
+    /// `inclusive` is set. Always returns `false` if `lo > hi`.
+    pub fn is_between(&self, lo: &Ratio<T>, hi: &Ratio<T>, inclusive: bool) -> bool {
+        if inclusive {
+            lo <= self && self <= hi
+        } else {
+            lo < self && self < hi
+        }
+    }
 
-impl<T: Clone + Integer> Ord for Ratio<T> {
-    #[inline]
-    fn cmp(&self, other: &Self) -> cmp::Ordering {
-        // With equal denominators, the numerators can be directly compared
-        if self.denom == other.denom {
-            let ord = self.numer.cmp(&other.numer);
-            return if self.denom < T::zero() {
-                ord.reverse()
-            } else {
-                ord
-            };
+    /// Returns `self` unchanged if its denominator is no larger than `max_denom`, or otherwise
+    /// the closest ratio with a denominator no larger than `max_denom`.
+    ///
+    /// The approximation is exact, using the continued fraction convergents of `self` and the
+    /// best semiconvergent. A tie is resolved in favor of the last convergent.
+    ///
+    /// **Panics if `max_denom` is less than one, or if `denom` is zero.**
+    pub fn clamp_denominator(&self, max_denom: &T) -> Ratio<T> {
+        assert!(*max_denom >= T::one(), "max_denom < 1");
+        if self.denom > T::zero() && self.denom <= *max_denom {
+            return self.clone();
+        }
+        let reduced = self.reduced();
+        if reduced.denom <= *max_denom {
+            return reduced;
         }
 
-        // With equal numerators, the denominators can be inversely compared
-        if self.numer == other.numer {
-            if self.numer.is_zero() {
-                return cmp::Ordering::Equal;
-            }
-            let ord = self.denom.cmp(&other.denom);
-            return if self.numer < T::zero() {
-                ord
-            } else {
-                ord.reverse()
-            };
+        let (conv, semi) = reduced.clamp_candidates(max_denom);
+        // The two candidates lie on either side of `self`, so whichever is closer is on the
+        // same side of their midpoint, which avoids subtracting `self` with its large
+        // denominator.
+        let mid = conv.midpoint(&semi);
+        let conv_closer = if conv < semi {
+            reduced <= mid
+        } else {
+            reduced >= mid
+        };
+        if conv_closer {
+            conv
+        } else {
+            semi
         }
+    }
 
-        // Unfortunately, we don't have CheckedMul to try.  That could sometimes avoid all the
-        // division below, or even always avoid it for BigInt and BigUint.
-        // FIXME- future breaking change to add Checked* to Integer?
+    /// Returns the last continued fraction convergent of the reduced `self` with a denominator
+    /// no larger than `max_denom`, and the best semiconvergent on the other side of `self`.
+    fn clamp_candidates(&self, max_denom: &T) -> (Ratio<T>, Ratio<T>) {
+        let mut conv = Convergents::new(self.continued_fraction());
+        // The denominator of `self` exceeds `max_denom`, so some term does too.
+        let (_, k) = conv
+            .clamp(max_denom, |_, _| true)
+            .expect("denominator out of range");
+        (conv.convergent(), conv.semiconvergent(&k))
+    }
 
-        // Compare as floored integers and remainders
-        let (self_int, self_rem) = self.numer.div_mod_floor(&self.denom);
-        let (other_int, other_rem) = other.numer.div_mod_floor(&other.denom);
-        match self_int.cmp(&other_int) {
-            cmp::Ordering::Greater => cmp::Ordering::Greater,
-            cmp::Ordering::Less => cmp::Ordering::Less,
-            cmp::Ordering::Equal => {
-                match (self_rem.is_zero(), other_rem.is_zero()) {
-                    (true, true) => cmp::Ordering::Equal,
-                    (true, false) => cmp::Ordering::Less,
-                    (false, true) => cmp::Ordering::Greater,
-                    (false, false) => {
-                        // Compare the reciprocals of the remaining fractions in reverse
-                        let self_recip = Ratio::new_raw(self.denom.clone(), self_rem);
-                        let other_recip = Ratio::new_raw(other.denom.clone(), other_rem);
-                        self_recip.cmp(&other_recip).reverse()
+    /// Returns an iterator over the best approximations of `target` with increasing
+    /// denominators up to `max_denom`, each strictly closer than the last.
+    ///
+    /// These are the distinct values of `target.clamp_denominator(&q)` for `q` from one to
+    /// `max_denom`, so the first is an integer and the last is the closest ratio within the
+    /// bound. They are drawn from the continued fraction convergents and semiconvergents of
+    /// `target`, and the iterator ends early once it reaches `target` itself.
+    ///
+    /// **Panics if `max_denom` is less than one, or if `target` has a zero denominator.**
+    pub fn approximations(target: &Ratio<T>, max_denom: T) -> impl Iterator<Item = Ratio<T>> {
+        assert!(max_denom >= T::one(), "max_denom < 1");
+        let target = target.reduced();
+        let mut best = target.clamp_denominator(&T::one());
+        let mut first = Some(best.clone());
+
+        let mut conv = Convergents::new(target.continued_fraction());
+        // The current term of the continued fraction, and the next multiple of it to try.
+        let (mut a, mut k) = (T::zero(), T::zero());
+        let mut next_term = true;
+        core::iter::from_fn(move || {
+            if let Some(first) = first.take() {
+                return Some(first);
+            }
+            loop {
+                if next_term {
+                    let term = conv.terms.next()?;
+                    // Semiconvergents below half the term are never closer than the last
+                    // convergent, and the first term only has the integer convergent.
+                    k = if conv.q1.is_zero() {
+                        term.clone()
+                    } else {
+                        cmp::max(term.clone() / (T::one() + T::one()), T::one())
+                    };
+                    a = term;
+                    next_term = false;
+                }
+                if let Some(max_k) = conv.max_multiple(&max_denom) {
+                    if k > max_k {
+                        return None;
                     }
                 }
-            }
-        }
-    }
-}
 
-impl<T: Clone + Integer> PartialOrd for Ratio<T> {
-    #[inline]
-    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        Some(self.cmp(other))
+                let candidate = if k == a {
+                    conv.push(a.clone());
+                    next_term = true;
+                    conv.convergent()
+                } else {
+                    let semi = conv.semiconvergent(&k);
+                    k = k.clone() + T::one();
+                    semi
+                };
+                if target.cmp_dist(&candidate, &best) == cmp::Ordering::Less {
+                    best = candidate.clone();
+                    return Some(candidate);
+                }
+            }
+        })
     }
-}
 
-impl<T: Clone + Integer> PartialEq for Ratio<T> {
-    #[inline]
-    fn eq(&self, other: &Self) -> bool {
-        self.cmp(other) == cmp::Ordering::Equal
+    /// Returns an iterator over the terms `a0, a1, a2, ...` of the continued fraction of
+    /// `self`, found by repeated `div_mod_floor` until the remainder is zero.
+    ///
+    /// The first term is the floor of `self`, so it is negative for negative values, and the
+    /// rest are positive. The last term of a non-integer is at least two, which makes the
+    /// expansion unique.
+    ///
+    /// **Panics if `denom` is zero.**
+    pub fn continued_fraction(&self) -> impl Iterator<Item = T> {
+        let reduced = self.reduced();
+        let (mut n, mut d) = (reduced.numer, reduced.denom);
+        core::iter::from_fn(move || {
+            if d.is_zero() {
+                return None;
+            }
+            let (a, rem) = n.div_mod_floor(&d);
+            n = core::mem::replace(&mut d, rem);
+            Some(a)
+        })
     }
-}
 
-impl<T: Clone + Integer> Eq for Ratio<T> {}
+    /// Creates the ratio with the continued fraction `terms`, reduced.
+    ///
+    /// This is the inverse of `continued_fraction`, computing the convergents from the first
+    /// term, so any reduced ratio round-trips exactly.
+    ///
+    /// **Panics if `terms` is empty, or if they give a zero denominator, as a zero term after
+    /// the first can.**
+    pub fn from_continued_fraction<I: IntoIterator<Item = T>>(terms: I) -> Ratio<T> {
+        let last = Convergents::new(terms.into_iter())
+            .last()
+            .expect("empty continued fraction");
+        Ratio::new(last.numer, last.denom)
+    }
 
-// NB: We can't just `#[derive(Hash)]`, because it needs to agree
-// with `Eq` even for non-reduced ratios.
-impl<T: Clone + Integer + Hash> Hash for Ratio<T> {
-    fn hash<H: Hasher>(&self, state: &mut H) {
-        recurse(&self.numer, &self.denom, state);
+    /// Returns an iterator over the continued fraction convergents of `self`, from the floor
+    /// of `self` to `self` itself, reduced.
+    ///
+    /// Each convergent `p_k/q_k` comes from the terms of `continued_fraction` by the
+    /// recurrence `p_k = a_k*p_{k-1} + p_{k-2}`, and likewise for `q_k`. Their denominators
+    /// increase, and they alternate below and above `self`, so stopping at a denominator bound
+    /// truncates an exact value like a `BigRational` to a good approximation.
+    ///
+    /// **Panics if `denom` is zero.**
+    pub fn convergents(&self) -> impl Iterator<Item = Ratio<T>> {
+        Convergents::new(self.continued_fraction())
+    }
 
-        fn recurse<T: Integer + Hash, H: Hasher>(numer: &T, denom: &T, state: &mut H) {
-            if !denom.is_zero() {
-                let (int, rem) = numer.div_mod_floor(denom);
-                int.hash(state);
-                recurse(denom, &rem, state);
-            } else {
-                denom.hash(state);
+    /// Returns the simplest ratio within `tol` of `self`, i.e. the one with the smallest
+    /// denominator, and of those the smallest magnitude.
+    ///
+    /// This is found with a Stern-Brocot search of the closed interval from `self - tol` to
+    /// `self + tol`.
+    ///
+    /// **Panics if `tol` is negative.**
+    pub fn simplest_within(&self, tol: &Ratio<T>) -> Ratio<T> {
+        let zero = Ratio::zero();
+        assert!(*tol >= zero, "tol < 0");
+        if *self >= zero {
+            if *self <= *tol {
+                return zero;
+            }
+        } else if zero.clone() - self <= *tol {
+            return zero;
+        }
+        Ratio::simplest_in(self - tol, Some(self + tol), true)
+    }
+
+    /// Returns the simplest ratio in the interval from `lo` to `hi`, including the endpoints if
+    /// `inclusive` is set, where no `hi` means the interval is unbounded. The interval must not
+    /// contain zero, though an open one may start or end at it.
+    fn simplest_in(lo: Ratio<T>, hi: Option<Ratio<T>>, inclusive: bool) -> Ratio<T> {
+        let zero = Ratio::zero();
+        if let Some(hi) = &hi {
+            if *hi <= zero {
+                let simplest =
+                    Ratio::simplest_in(zero.clone() - hi, Some(zero.clone() - lo), inclusive);
+                return zero - simplest;
             }
         }
+        let floor = lo.floor();
+        if inclusive && floor == lo {
+            return lo;
+        }
+        let next = floor.clone() + Ratio::one();
+        let hi = match hi {
+            Some(hi) if next > hi || (next == hi && !inclusive) => hi,
+            _ => return next,
+        };
+        // Both bounds share the integer part, so continue with the reciprocals of the
+        // fractional parts, which swaps their order. A zero fractional part of `lo`, which an
+        // open interval excludes, leaves the reciprocal interval unbounded.
+        let upper = if lo == floor {
+            None
+        } else {
+            Some((lo - floor.clone()).recip())
+        };
+        let frac = Ratio::simplest_in((hi - floor.clone()).recip(), upper, inclusive);
+        floor + frac.recip()
     }
-}
-
-mod iter_sum_product {
-    use crate::Ratio;
-    use core::iter::{Product, Sum};
-    use num_integer::Integer;
-    use num_traits::{One, Zero};
 
-    impl<T: Integer + Clone> Sum for Ratio<T> {
-        fn sum<I>(iter: I) -> Self
-        where
-            I: Iterator<Item = Ratio<T>>,
-        {
-            iter.fold(Self::zero(), |sum, num| sum + num)
+    /// Returns the simplest ratio strictly between `lo` and `hi`, i.e. the one with the smallest
+    /// denominator, and of those the smallest magnitude, or `None` if `lo >= hi`.
+    ///
+    /// This is found with a Stern-Brocot search of the open interval.
+    pub fn simplest_between(lo: &Ratio<T>, hi: &Ratio<T>) -> Option<Ratio<T>> {
+        if lo >= hi {
+            return None;
         }
+        let zero = Ratio::zero();
+        if *lo < zero && *hi > zero {
+            return Some(zero);
+        }
+        Some(Ratio::simplest_in(lo.clone(), Some(hi.clone()), false))
     }
 
-    impl<'a, T: Integer + Clone> Sum<&'a Ratio<T>> for Ratio<T> {
-        fn sum<I>(iter: I) -> Self
-        where
-            I: Iterator<Item = &'a Ratio<T>>,
-        {
-            iter.fold(Self::zero(), |sum, num| sum + num)
+    /// Returns the value halfway between `self` and `other`.
+    ///
+    /// For values of the same sign, half the difference is added to the smaller one, and for
+    /// values of opposite signs the sum is halved. So neither a sum nor a difference of two
+    /// large values of the same magnitude is formed, and unsigned types never need a negative
+    /// intermediate.
+    pub fn midpoint(&self, other: &Ratio<T>) -> Ratio<T> {
+        let two = T::one() + T::one();
+        let (lo, hi) = if self <= other {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        if lo.cmp_zero() == cmp::Ordering::Less && hi.cmp_zero() == cmp::Ordering::Greater {
+            (lo + hi) / two
+        } else {
+            lo + (hi - lo) / two
         }
     }
 
-    impl<T: Integer + Clone> Product for Ratio<T> {
-        fn product<I>(iter: I) -> Self
-        where
-            I: Iterator<Item = Ratio<T>>,
-        {
-            iter.fold(Self::one(), |prod, num| prod * num)
+    /// Compares the distances `|self - a|` and `|self - b|`, returning `Less` if `a` is closer.
+    ///
+    /// If `a` and `b` are on the same side of `self`, this just compares them, without any
+    /// arithmetic. Otherwise it compares `self` with their `midpoint`, rather than computing
+    /// both distances, which doesn't overflow even for `a` and `b` at opposite extremes.
+    pub fn cmp_dist(&self, a: &Ratio<T>, b: &Ratio<T>) -> cmp::Ordering {
+        use cmp::Ordering::*;
+
+        match (a.cmp(self), b.cmp(self)) {
+            (Equal, Equal) => Equal,
+            (Equal, _) => Less,
+            (_, Equal) => Greater,
+            (Greater, Greater) => a.cmp(b),
+            (Less, Less) => b.cmp(a),
+            (Less, Greater) => self.cmp(&a.midpoint(b)),
+            (Greater, Less) => a.midpoint(b).cmp(self),
         }
     }
 
-    impl<'a, T: Integer + Clone> Product<&'a Ratio<T>> for Ratio<T> {
-        fn product<I>(iter: I) -> Self
-        where
-            I: Iterator<Item = &'a Ratio<T>>,
-        {
-            iter.fold(Self::one(), |prod, num| prod * num)
+    /// Returns `self * a + b`, reducing only once at the end.
+    ///
+    /// The product cancels common factors before multiplying, like `Mul`, and the sum
+    /// cancels `gcd(b.denom, product.denom)` before forming the denominator, like `Add`, so
+    /// this overflows no sooner than the separate operations. It's convenient for Horner's
+    /// method, e.g. `coeffs.iter().fold(zero, |acc, c| acc.mul_add(&x, c))`.
+    pub fn mul_add(&self, a: &Ratio<T>, b: &Ratio<T>) -> Ratio<T> {
+        let gcd_ad = self.numer.gcd(&a.denom);
+        let gcd_bc = self.denom.gcd(&a.numer);
+        let numer = self.numer.clone() / gcd_ad.clone() * (a.numer.clone() / gcd_bc.clone());
+        let denom = self.denom.clone() / gcd_bc * (a.denom.clone() / gcd_ad);
+
+        let g = denom.gcd(&b.denom);
+        let lhs_factor = denom / g.clone();
+        let numer = numer * (b.denom.clone() / g.clone()) + b.numer.clone() * lhs_factor.clone();
+        let g2 = numer.gcd(&g);
+        Ratio::new(numer / g2.clone(), lhs_factor * (b.denom.clone() / g2))
+    }
+
+    /// Returns the 2D cross product `ax*by - ay*bx` of the vectors `(ax, ay)` and `(bx, by)`.
+    ///
+    /// This is twice the signed area of the triangle they span, positive if `b` is
+    /// counter-clockwise from `a`. The products cancel common factors before multiplying, and
+    /// the difference cancels them before forming the denominator, so fixed-size types only
+    /// overflow if the reduced intermediate products or the result don't fit.
+    pub fn cross(ax: &Ratio<T>, ay: &Ratio<T>, bx: &Ratio<T>, by: &Ratio<T>) -> Ratio<T> {
+        ax * by - ay * bx
+    }
+
+    /// Bisects the interval between `lo` and `hi` to find where `f` changes its result,
+    /// returning a value within `tol` of that point.
+    ///
+    /// `f` is expected to be monotonic, like the sign of a function compared to zero, with
+    /// different results at `lo` and `hi`. If it returns `Equal` at a probed point, that point
+    /// is returned directly.
+    ///
+    /// **Panics if `tol` is not positive.**
+    pub fn bisect<F: Fn(&Ratio<T>) -> cmp::Ordering>(
+        mut lo: Ratio<T>,
+        mut hi: Ratio<T>,
+        tol: &Ratio<T>,
+        f: F,
+    ) -> Ratio<T> {
+        assert!(*tol > Ratio::zero(), "tol <= 0");
+        if lo > hi {
+            core::mem::swap(&mut lo, &mut hi);
+        }
+        let lo_ord = f(&lo);
+        if lo_ord == cmp::Ordering::Equal {
+            return lo;
+        }
+        while &hi - &lo > *tol {
+            let mid = lo.midpoint(&hi);
+            let ord = f(&mid);
+            if ord == cmp::Ordering::Equal {
+                return mid;
+            } else if ord == lo_ord {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
         }
+        lo.midpoint(&hi)
     }
-}
 
-mod opassign {
-    use core::ops::{AddAssign, DivAssign, MulAssign, RemAssign, SubAssign};
+    /// Returns the positive difference `self - other`, or zero if `self <= other`.
+    ///
+    /// This matches `Signed::abs_sub`, but doesn't require a signed element type, since it never
+    /// subtracts the larger value from the smaller.
+    #[inline]
+    pub fn abs_sub(&self, other: &Ratio<T>) -> Ratio<T> {
+        if self <= other {
+            Ratio::zero()
+        } else {
+            self - other
+        }
+    }
 
-    use crate::Ratio;
-    use num_integer::Integer;
-    use num_traits::NumAssign;
+    /// Divides the fraction `num` by the fraction `den`, simplifying the compound fraction
+    /// `(num)/(den)` to a single reduced `Ratio`.
+    ///
+    /// This is the same as `num / den`, which cancels common factors across the two ratios
+    /// before multiplying, but names the outer division explicitly.
+    ///
+    /// **Panics if `den` is zero.**
+    #[inline]
+    pub fn div_ratios(num: &Ratio<T>, den: &Ratio<T>) -> Ratio<T> {
+        num / den
+    }
 
-    impl<T: Clone + Integer + NumAssign> AddAssign for Ratio<T> {
-        fn add_assign(&mut self, other: Ratio<T>) {
-            if self.denom == other.denom {
-                self.numer += other.numer
-            } else {
-                let lcm = self.denom.lcm(&other.denom);
-                let lhs_numer = self.numer.clone() * (lcm.clone() / self.denom.clone());
-                let rhs_numer = other.numer * (lcm.clone() / other.denom);
-                self.numer = lhs_numer + rhs_numer;
-                self.denom = lcm;
-            }
-            self.reduce();
+    /// Linearly interpolates `x` between the bracketing breakpoints of a table of `(x, y)`
+    /// points, exactly.
+    ///
+    /// Returns `None` if the table is empty, if its `x` values are not strictly increasing, or
+    /// if `x` is outside the range of the table.
+    pub fn interpolate(points: &[(Ratio<T>, Ratio<T>)], x: &Ratio<T>) -> Option<Ratio<T>> {
+        if points.windows(2).any(|w| w[0].0 >= w[1].0) {
+            return None;
         }
+        if let [(x0, y0)] = points {
+            return if x == x0 { Some(y0.clone()) } else { None };
+        }
+        let w = points.windows(2).find(|w| w[0].0 <= *x && *x <= w[1].0)?;
+        let ((x0, y0), (x1, y1)) = (&w[0], &w[1]);
+        // 0 <= t <= 1, and the slope is split by sign so this also works for unsigned types
+        let t = (x - x0) / (x1 - x0);
+        Some(if y1 >= y0 {
+            y0 + (y1 - y0) * t
+        } else {
+            y0 - (y0 - y1) * t
+        })
     }
 
-    // (a/b) / (c/d) = (a/gcd_ac)*(d/gcd_bd) / ((c/gcd_ac)*(b/gcd_bd))
-    impl<T: Clone + Integer + NumAssign> DivAssign for Ratio<T> {
-        fn div_assign(&mut self, other: Ratio<T>) {
-            let gcd_ac = self.numer.gcd(&other.numer);
-            let gcd_bd = self.denom.gcd(&other.denom);
-            self.numer /= gcd_ac.clone();
-            self.numer *= other.denom / gcd_bd.clone();
-            self.denom /= gcd_bd;
-            self.denom *= other.numer / gcd_ac;
-            self.reduce(); // TODO: remove this line. see #8.
+    /// Returns an iterator over the decimal expansion: first the integer part, rounded towards
+    /// zero, then the digits of the fractional part one at a time.
+    ///
+    /// The iterator ends after the last non-zero digit of a terminating decimal, and is
+    /// infinite otherwise.
+    pub fn decimal_digits(&self) -> DecimalDigits<T> {
+        let (integer, mut rem) = self.numer.div_rem(&self.denom);
+        // Only the magnitude matters for the digits, so give `rem` the sign of `denom`, which
+        // also keeps unsigned types from needing a negative remainder.
+        if (rem < T::zero()) != (self.denom < T::zero()) && !rem.is_zero() {
+            rem = T::zero() - rem;
+        }
+        DecimalDigits {
+            integer: Some(integer),
+            rem,
+            denom: self.denom.clone(),
         }
     }
 
-    // a/b * c/d = (a/gcd_ad)*(c/gcd_bc) / ((d/gcd_ad)*(b/gcd_bc))
-    impl<T: Clone + Integer + NumAssign> MulAssign for Ratio<T> {
-        fn mul_assign(&mut self, other: Ratio<T>) {
-            let gcd_ad = self.numer.gcd(&other.denom);
-            let gcd_bc = self.denom.gcd(&other.numer);
-            self.numer /= gcd_ad.clone();
-            self.numer *= other.numer / gcd_bc.clone();
-            self.denom /= gcd_bc;
-            self.denom *= other.denom / gcd_ad;
-            self.reduce(); // TODO: remove this line. see #8.
+    /// Returns an iterator over the differences of consecutive items, `x[i+1] - x[i]`.
+    ///
+    /// This yields one item fewer than the input, or nothing for fewer than two items.
+    pub fn diffs<I: IntoIterator<Item = Ratio<T>>>(iter: I) -> impl Iterator<Item = Ratio<T>> {
+        let mut iter = iter.into_iter();
+        let mut prev = iter.next();
+        core::iter::from_fn(move || {
+            let next = iter.next()?;
+            let diff = &next - prev.as_ref()?;
+            prev = Some(next);
+            Some(diff)
+        })
+    }
+
+    /// Returns an iterator over `start`, `start + step`, `start + 2*step`, ... up to `stop`,
+    /// which is only included if `inclusive` is true.
+    ///
+    /// A negative `step` counts down towards `stop` instead. The iterator is empty if `stop` is
+    /// not in the direction of `step` from `start`. It can also be reversed, yielding the last
+    /// value first and ending at `start`. The last value is found from the remainders of
+    /// `start` and `stop` modulo `step`, so neither `stop - start` nor the number of steps has
+    /// to fit in `T`.
+    ///
+    /// **Panics if `step` is zero.**
+    pub fn ranged(
+        start: Ratio<T>,
+        stop: Ratio<T>,
+        step: Ratio<T>,
+        inclusive: bool,
+    ) -> impl DoubleEndedIterator<Item = Ratio<T>> {
+        assert!(!step.is_zero(), "step == 0");
+        Ranged {
+            descending: step < Ratio::zero(),
+            next: Some(start),
+            stop,
+            step,
+            inclusive,
+            snapped: false,
         }
     }
+}
 
-    impl<T: Clone + Integer + NumAssign> RemAssign for Ratio<T> {
-        fn rem_assign(&mut self, other: Ratio<T>) {
-            if self.denom == other.denom {
-                self.numer %= other.numer
-            } else {
-                let lcm = self.denom.lcm(&other.denom);
-                let lhs_numer = self.numer.clone() * (lcm.clone() / self.denom.clone());
-                let rhs_numer = other.numer * (lcm.clone() / other.denom);
-                self.numer = lhs_numer % rhs_numer;
-                self.denom = lcm;
+/// An iterator over the decimal expansion of a `Ratio`, created by [`Ratio::decimal_digits`].
+///
+/// The first item is the integer part, which carries the sign, and each further item is a digit
+/// from 0 to 9 of the fractional part's magnitude. Since the integer part of a value between -1
+/// and 0 is zero, the sign of such values has to be checked separately.
+#[derive(Clone, Debug)]
+pub struct DecimalDigits<T> {
+    integer: Option<T>,
+    rem: T,
+    denom: T,
+}
+
+impl<T: Clone + Integer> Iterator for DecimalDigits<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        if let Some(integer) = self.integer.take() {
+            return Some(integer);
+        }
+        if self.rem.is_zero() {
+            return None;
+        }
+        // Multiply the remainder by ten with repeated addition modulo `denom`, so `rem * 10`
+        // never has to fit, counting how often it wraps around as the digit.
+        let rem = core::mem::replace(&mut self.rem, T::zero());
+        let mut digit = T::zero();
+        for _ in 0..10 {
+            let (sum, wrapped) = add_mod(self.rem.clone(), rem.clone(), &self.denom);
+            self.rem = sum;
+            if wrapped {
+                digit = digit + T::one();
             }
-            self.reduce();
         }
+        Some(digit)
     }
+}
 
-    impl<T: Clone + Integer + NumAssign> SubAssign for Ratio<T> {
-        fn sub_assign(&mut self, other: Ratio<T>) {
-            if self.denom == other.denom {
-                self.numer -= other.numer
-            } else {
-                let lcm = self.denom.lcm(&other.denom);
-                let lhs_numer = self.numer.clone() * (lcm.clone() / self.denom.clone());
-                let rhs_numer = other.numer * (lcm.clone() / other.denom);
-                self.numer = lhs_numer - rhs_numer;
-                self.denom = lcm;
-            }
-            self.reduce();
+struct Ranged<T> {
+    next: Option<Ratio<T>>,
+    stop: Ratio<T>,
+    step: Ratio<T>,
+    inclusive: bool,
+    descending: bool,
+    /// Whether `stop` has been moved onto the values, as the last one, which is only needed
+    /// to iterate from the back.
+    snapped: bool,
+}
+
+impl<T: Clone + Integer> Ranged<T> {
+    /// Returns true if `a` comes before `b` in the direction of `step`.
+    fn before(&self, a: &Ratio<T>, b: &Ratio<T>) -> bool {
+        if self.descending {
+            a > b
+        } else {
+            a < b
         }
     }
 
-    // a/b + c/1 = (a*1 + b*c) / (b*1) = (a + b*c) / b
-    impl<T: Clone + Integer + NumAssign> AddAssign<T> for Ratio<T> {
-        fn add_assign(&mut self, other: T) {
-            self.numer += self.denom.clone() * other;
-            self.reduce();
+    /// Moves `stop` back onto the last value, or returns false if there are no values.
+    ///
+    /// This subtracts the remainder of `stop - next` modulo `step`, found from the remainders
+    /// of both ends, since the difference or the number of steps may not fit in `T`.
+    fn snap(&mut self) -> bool {
+        let next = match &self.next {
+            Some(next) => next,
+            None => return false,
+        };
+        if self.before(&self.stop, next) {
+            return false;
+        }
+        let (a, b) = (grid_rem(next, &self.step), grid_rem(&self.stop, &self.step));
+        let rem = if self.before(&b, &a) {
+            &self.step - (a - b)
+        } else {
+            b - a
+        };
+        if !rem.is_zero() {
+            self.stop = &self.stop - rem;
+        } else if !self.inclusive {
+            if self.stop == *next {
+                return false;
+            }
+            self.stop = &self.stop - &self.step;
         }
+        self.inclusive = true;
+        self.snapped = true;
+        true
     }
+}
 
-    impl<T: Clone + Integer + NumAssign> DivAssign<T> for Ratio<T> {
-        fn div_assign(&mut self, other: T) {
-            let gcd = self.numer.gcd(&other);
-            self.numer /= gcd.clone();
-            self.denom *= other / gcd;
-            self.reduce(); // TODO: remove this line. see #8.
+impl<T: Clone + Integer> Iterator for Ranged<T> {
+    type Item = Ratio<T>;
+
+    fn next(&mut self) -> Option<Ratio<T>> {
+        let current = self.next.take()?;
+        let order = if self.descending {
+            self.stop.cmp(&current)
+        } else {
+            current.cmp(&self.stop)
+        };
+        match order {
+            cmp::Ordering::Less => {
+                self.next = Some(&current + &self.step);
+                Some(current)
+            }
+            cmp::Ordering::Equal if self.inclusive => Some(current),
+            _ => None,
         }
     }
+}
 
-    impl<T: Clone + Integer + NumAssign> MulAssign<T> for Ratio<T> {
-        fn mul_assign(&mut self, other: T) {
-            let gcd = self.denom.gcd(&other);
-            self.denom /= gcd.clone();
-            self.numer *= other / gcd;
-            self.reduce(); // TODO: remove this line. see #8.
+impl<T: Clone + Integer> DoubleEndedIterator for Ranged<T> {
+    fn next_back(&mut self) -> Option<Ratio<T>> {
+        if !self.snapped && !self.snap() {
+            self.next = None;
+            return None;
+        }
+        let next = self.next.as_ref()?;
+        let last = self.stop.clone();
+        if last == *next {
+            self.next = None;
+        } else {
+            self.stop = &last - &self.step;
         }
+        Some(last)
     }
+}
 
-    // a/b % c/1 = (a*1 % b*c) / (b*1) = (a % b*c) / b
-    impl<T: Clone + Integer + NumAssign> RemAssign<T> for Ratio<T> {
-        fn rem_assign(&mut self, other: T) {
-            self.numer %= self.denom.clone() * other;
-            self.reduce();
+/// The convergents `p_k/q_k` of the continued fraction `terms`, by the recurrence
+/// `p_k = a_k*p_{k-1} + p_{k-2}`, and likewise for `q_k`.
+///
+/// The last two convergents are kept as `p0/q0` and `p1/q1`, starting from `0/1` and `1/0`.
+/// Consecutive convergents satisfy `p1*q0 - p0*q1 = ±1`, so the convergents and the
+/// semiconvergents between them are already reduced.
+struct Convergents<T, I> {
+    terms: I,
+    p0: T,
+    q0: T,
+    p1: T,
+    q1: T,
+}
+
+impl<T: Clone + Integer, I: Iterator<Item = T>> Convergents<T, I> {
+    fn new(terms: I) -> Self {
+        Convergents {
+            terms,
+            p0: T::zero(),
+            q0: T::one(),
+            p1: T::one(),
+            q1: T::zero(),
         }
     }
 
-    // a/b - c/1 = (a*1 - b*c) / (b*1) = (a - b*c) / b
-    impl<T: Clone + Integer + NumAssign> SubAssign<T> for Ratio<T> {
-        fn sub_assign(&mut self, other: T) {
-            self.numer -= self.denom.clone() * other;
-            self.reduce();
+    /// Steps to the next convergent with the term `a`.
+    fn push(&mut self, a: T) {
+        let p2 = self.p0.clone() + a.clone() * self.p1.clone();
+        let q2 = self.q0.clone() + a * self.q1.clone();
+        self.p0 = core::mem::replace(&mut self.p1, p2);
+        self.q0 = core::mem::replace(&mut self.q1, q2);
+    }
+
+    /// Returns the last convergent.
+    fn convergent(&self) -> Ratio<T> {
+        Ratio::new_raw(self.p1.clone(), self.q1.clone())
+    }
+
+    /// Returns the semiconvergent `(p0 + k*p1)/(q0 + k*q1)`.
+    fn semiconvergent(&self, k: &T) -> Ratio<T> {
+        Ratio::new_raw(
+            self.p0.clone() + k.clone() * self.p1.clone(),
+            self.q0.clone() + k.clone() * self.q1.clone(),
+        )
+    }
+
+    /// Returns the largest `k` with `q0 + k*q1` no larger than `max_denom`, without
+    /// overflowing to compute that denominator, or `None` before the first term.
+    fn max_multiple(&self, max_denom: &T) -> Option<T> {
+        if self.q1.is_zero() {
+            None
+        } else {
+            Some((max_denom.clone() - self.q0.clone()) / self.q1.clone())
         }
     }
 
-    macro_rules! forward_op_assign {
-        (impl $imp:ident, $method:ident) => {
-            impl<'a, T: Clone + Integer + NumAssign> $imp<&'a Ratio<T>> for Ratio<T> {
-                #[inline]
-                fn $method(&mut self, other: &Ratio<T>) {
-                    self.$method(other.clone())
+    /// Steps through the convergents with a denominator no larger than `max_denom`, and
+    /// returns the first term that exceeds it along with the largest multiple `k` of it that
+    /// doesn't, or `None` if the terms run out or `fits` rejects the next term first.
+    fn clamp(&mut self, max_denom: &T, mut fits: impl FnMut(&Self, &T) -> bool) -> Option<(T, T)> {
+        while let Some(a) = self.terms.next() {
+            if let Some(k) = self.max_multiple(max_denom) {
+                if k < a {
+                    return Some((a, k));
                 }
             }
-            impl<'a, T: Clone + Integer + NumAssign> $imp<&'a T> for Ratio<T> {
-                #[inline]
-                fn $method(&mut self, other: &T) {
-                    self.$method(other.clone())
-                }
+            if !fits(self, &a) {
+                return None;
             }
-        };
+            self.push(a);
+        }
+        None
     }
 
-    forward_op_assign!(impl AddAssign, add_assign);
-    forward_op_assign!(impl DivAssign, div_assign);
-    forward_op_assign!(impl MulAssign, mul_assign);
-    forward_op_assign!(impl RemAssign, rem_assign);
-    forward_op_assign!(impl SubAssign, sub_assign);
+    /// Returns the numerator `p0 + k*p1` of the next convergent with the term `k`, or of a
+    /// semiconvergent with the multiple `k`, or `None` if it overflows.
+    fn checked_numer(&self, k: &T) -> Option<T>
+    where
+        T: CheckedAdd + CheckedMul,
+    {
+        k.checked_mul(&self.p1)?.checked_add(&self.p0)
+    }
 }
 
-macro_rules! forward_ref_ref_binop {
-    (impl $imp:ident, $method:ident) => {
-        impl<'a, 'b, T: Clone + Integer> $imp<&'b Ratio<T>> for &'a Ratio<T> {
-            type Output = Ratio<T>;
+impl<T: Clone + Integer, I: Iterator<Item = T>> Iterator for Convergents<T, I> {
+    type Item = Ratio<T>;
 
-            #[inline]
-            fn $method(self, other: &'b Ratio<T>) -> Ratio<T> {
-                self.clone().$method(other.clone())
-            }
-        }
-        impl<'a, 'b, T: Clone + Integer> $imp<&'b T> for &'a Ratio<T> {
-            type Output = Ratio<T>;
+    fn next(&mut self) -> Option<Ratio<T>> {
+        let a = self.terms.next()?;
+        self.push(a);
+        Some(self.convergent())
+    }
+}
 
-            #[inline]
-            fn $method(self, other: &'b T) -> Ratio<T> {
-                self.clone().$method(other.clone())
-            }
-        }
+impl<T: Clone + Integer + Into<i128>> Ratio<T> {
+    /// Returns the reduced numerator and denominator widened to `i128`.
+    ///
+    /// Equal ratios always produce equal keys, independent of their representation or of the
+    /// platform, so the result is suitable as a portable hash or database key. The widening
+    /// happens before reduction, so this can't overflow for `T::MIN` numerators of narrower
+    /// types. An `i128` isn't widened, so it overflows if the reduced value isn't representable,
+    /// like `i128::MIN / -1`.
+    ///
+    /// **Panics if `denom` is zero, or on that `i128` overflow when it is checked.**
+    pub fn to_canonical_key(&self) -> (i128, i128) {
+        Ratio::new(self.numer.clone().into(), self.denom.clone().into()).into_raw()
+    }
+}
+
+// FIXME(#5992): assignment operator overloads
+// T: Clone + Integer != T: Clone + NumAssign
+#[inline]
+fn replace_with<T: Zero>(x: &mut T, f: impl FnOnce(T) -> T) {
+    let y = core::mem::replace(x, T::zero());
+    *x = f(y);
+}
+
+/// Returns `x` modulo `step`, between zero and `step`, with `step` excluded.
+///
+/// For `x = n/d` and `step = p/q` in lowest terms, this is `(n*q mod d*p) / (d*q)`, with the
+/// product reduced by `mul_mod`, so `x / step` never has to fit in `T`.
+fn grid_rem<T: Clone + Integer>(x: &Ratio<T>, step: &Ratio<T>) -> Ratio<T> {
+    let (x, step) = (x.reduced(), step.reduced());
+    let modulus = x.denom.clone() * step.numer;
+    // `MIN % -1` overflows, although the result is zero.
+    let numer = if modulus < T::zero() && (modulus.clone() + T::one()).is_zero() {
+        T::zero()
+    } else {
+        x.numer.mod_floor(&modulus)
     };
+    let rem = mul_mod(numer, step.denom.clone(), &modulus);
+    Ratio::new(rem, x.denom) / Ratio::from_integer(step.denom)
 }
 
-macro_rules! forward_ref_val_binop {
-    (impl $imp:ident, $method:ident) => {
-        impl<'a, T> $imp<Ratio<T>> for &'a Ratio<T>
-        where
-            T: Clone + Integer,
-        {
-            type Output = Ratio<T>;
+/// Returns `x + y` modulo `m`, for `x` and `y` between zero and `m`, with `m` excluded, and
+/// whether the sum wrapped around. Nothing larger in magnitude than `m` is formed.
+fn add_mod<T: Clone + Integer>(x: T, y: T, m: &T) -> (T, bool) {
+    let rest = m.clone() - y.clone();
+    let wraps = if *m > T::zero() { x >= rest } else { x <= rest };
+    if wraps {
+        (x - rest, true)
+    } else {
+        (x + y, false)
+    }
+}
 
-            #[inline]
-            fn $method(self, other: Ratio<T>) -> Ratio<T> {
-                self.clone().$method(other)
-            }
+/// Returns `x * k` modulo `m`, for `x` between zero and `m`, with `m` excluded, and a
+/// non-negative `k`. The product is built by doubling, one bit of `k` at a time.
+fn mul_mod<T: Clone + Integer>(x: T, mut k: T, m: &T) -> T {
+    let two = T::one() + T::one();
+    let (mut acc, mut base) = (T::zero(), x);
+    while !k.is_zero() {
+        if k.is_odd() {
+            acc = add_mod(acc, base.clone(), m).0;
         }
-        impl<'a, T> $imp<T> for &'a Ratio<T>
-        where
-            T: Clone + Integer,
-        {
-            type Output = Ratio<T>;
-
-            #[inline]
-            fn $method(self, other: T) -> Ratio<T> {
-                self.clone().$method(other)
-            }
+        k = k / two.clone();
+        if !k.is_zero() {
+            base = add_mod(base.clone(), base, m).0;
         }
-    };
+    }
+    acc
 }
 
-macro_rules! forward_val_ref_binop {
-    (impl $imp:ident, $method:ident) => {
-        impl<'a, T> $imp<&'a Ratio<T>> for Ratio<T>
-        where
-            T: Clone + Integer,
-        {
-            type Output = Ratio<T>;
+fn ten<T: Clone + Integer>() -> T {
+    let two = T::one() + T::one();
+    let five = two.clone() + two.clone() + T::one();
+    two * five
+}
 
-            #[inline]
-            fn $method(self, other: &Ratio<T>) -> Ratio<T> {
-                self.$method(other.clone())
-            }
+/// Splits a finite float into `numer * 2^exponent` with an odd `numer`, or `(0, 0)` for zero.
+///
+/// Every finite float is such a dyadic rational, and the mantissa has at most 53 bits, so
+/// `numer` always fits.
+fn dyadic_parts(f: f64) -> (i64, i32) {
+    let (mantissa, exponent, sign) = f.integer_decode();
+    if mantissa == 0 {
+        return (0, 0);
+    }
+    let shift = mantissa.trailing_zeros();
+    let numer = (mantissa >> shift) as i64 * sign as i64;
+    (numer, exponent as i32 + shift as i32)
+}
+
+#[cfg(feature = "num-bigint")]
+impl Ratio<BigInt> {
+    /// Converts a float into a rational number.
+    pub fn from_float<T: FloatCore>(f: T) -> Option<BigRational> {
+        if !f.is_finite() {
+            return None;
         }
-        impl<'a, T> $imp<&'a T> for Ratio<T>
-        where
-            T: Clone + Integer,
-        {
-            type Output = Ratio<T>;
+        let (mantissa, exponent, sign) = f.integer_decode();
+        let bigint_sign = if sign == 1 { Sign::Plus } else { Sign::Minus };
+        if exponent < 0 {
+            let one: BigInt = One::one();
+            let denom: BigInt = one << ((-exponent) as usize);
+            let numer: BigUint = FromPrimitive::from_u64(mantissa).unwrap();
+            Some(Ratio::new(BigInt::from_biguint(bigint_sign, numer), denom))
+        } else {
+            let mut numer: BigUint = FromPrimitive::from_u64(mantissa).unwrap();
+            numer <<= exponent as usize;
+            Some(Ratio::from_integer(BigInt::from_biguint(
+                bigint_sign,
+                numer,
+            )))
+        }
+    }
 
-            #[inline]
-            fn $method(self, other: &T) -> Ratio<T> {
-                self.$method(other.clone())
-            }
+    /// Compares two big rationals with a single cross-multiplication, `a*d` against `c*b`.
+    ///
+    /// This gives the same result as `Ord::cmp`, but since `BigInt` products can't overflow it
+    /// avoids the generic algorithm's repeated divisions and intermediate ratios, which is
+    /// usually much faster. (`Ord` itself can't be specialized for `BigInt`.)
+    pub fn cmp_cross(&self, other: &BigRational) -> cmp::Ordering {
+        let ord = (&self.numer * &other.denom).cmp(&(&other.numer * &self.denom));
+        if self.denom.is_negative() != other.denom.is_negative() {
+            ord.reverse()
+        } else {
+            ord
         }
-    };
+    }
+
+    /// Converts to the nearest `f64`, along with the exact residual `self - value`.
+    ///
+    /// Adding the residual back to the exact rational value of the float reproduces `self`.
+    /// Returns `None` if the nearest float is not finite.
+    pub fn to_f64_with_residual(&self) -> Option<(f64, BigRational)> {
+        let value = self.to_f64()?;
+        let residual = self - Ratio::from_float(value)?;
+        Some((value, residual))
+    }
+
+    /// Converts to the closest `Rational64`, saturating to `i64::MIN` or `i64::MAX` when
+    /// `self` is out of range.
+    ///
+    /// Values whose reduced parts fit in an `i64` are converted exactly. Otherwise this finds
+    /// the closest ratio with both parts in range, as `clamp_denominator` does for the
+    /// denominator alone. A tie is resolved in favor of the continued fraction convergent.
+    pub fn clamp_into_i64(&self) -> Rational64 {
+        let reduced = self.reduced();
+        if let (Some(numer), Some(denom)) = (reduced.numer.to_i64(), reduced.denom.to_i64()) {
+            return Ratio::new_raw(numer, denom);
+        }
+
+        let negative = reduced.numer.is_negative();
+        let max = BigInt::from(i64::MAX);
+        let mag = reduced.abs();
+        if mag >= Ratio::from_integer(max.clone()) {
+            let bound = if negative { i64::MIN } else { i64::MAX };
+            return Ratio::from_integer(bound);
+        }
+
+        let approx = if mag < Ratio::one() {
+            // The numerator is smaller than the denominator.
+            mag.clamp_denominator(&max)
+        } else {
+            // The denominator is smaller than the numerator, so bound the numerator through the
+            // reciprocal. Neither candidate is zero, since `1/mag` is larger than `1/max`.
+            let (conv, semi) = mag.recip().clamp_candidates(&max);
+            let (conv, semi) = (conv.recip(), semi.recip());
+            if mag.cmp_dist(&conv, &semi) == cmp::Ordering::Greater {
+                semi
+            } else {
+                conv
+            }
+        };
+        let numer = approx.numer.to_i64().expect("numerator in range");
+        let denom = approx.denom.to_i64().expect("denominator in range");
+        Ratio::new_raw(if negative { -numer } else { numer }, denom)
+    }
 }
 
-macro_rules! forward_all_binop {
-    (impl $imp:ident, $method:ident) => {
-        forward_ref_ref_binop!(impl $imp, $method);
-        forward_ref_val_binop!(impl $imp, $method);
-        forward_val_ref_binop!(impl $imp, $method);
-    };
+impl Ratio<i64> {
+    /// Converts a float into the exactly equal `Rational64`, if there is one.
+    ///
+    /// Every finite float is a dyadic rational, but this returns `None` when its numerator or
+    /// power-of-two denominator doesn't fit in an `i64`, as well as for infinities and NaN.
+    pub fn from_f64_if_exact_i64(x: f64) -> Option<Rational64> {
+        if !x.is_finite() {
+            return None;
+        }
+        let (numer, exponent) = dyadic_parts(x);
+        if exponent < 0 {
+            let denom = 2i64.checked_pow((-exponent) as u32)?;
+            Some(Ratio::new_raw(numer, denom))
+        } else {
+            // Doubling the signed numerator step by step reaches `i64::MIN` without overflow.
+            let numer = (0..exponent).try_fold(numer, |n, _| n.checked_mul(2))?;
+            Some(Ratio::new_raw(numer, 1))
+        }
+    }
 }
 
-// Arithmetic
-forward_all_binop!(impl Mul, mul);
-// a/b * c/d = (a/gcd_ad)*(c/gcd_bc) / ((d/gcd_ad)*(b/gcd_bc))
-impl<T> Mul<Ratio<T>> for Ratio<T>
-where
-    T: Clone + Integer,
-{
-    type Output = Ratio<T>;
-    #[inline]
-    fn mul(self, rhs: Ratio<T>) -> Ratio<T> {
-        let gcd_ad = self.numer.gcd(&rhs.denom);
-        let gcd_bc = self.denom.gcd(&rhs.numer);
-        Ratio::new(
-            self.numer / gcd_ad.clone() * (rhs.numer / gcd_bc.clone()),
-            self.denom / gcd_bc * (rhs.denom / gcd_ad),
-        )
+impl<T: Clone + Integer> Default for Ratio<T> {
+    /// Returns zero
+    fn default() -> Self {
+        Ratio::zero()
     }
 }
-// a/b * c/1 = (a*c) / (b*1) = (a*c) / b
-impl<T> Mul<T> for Ratio<T>
+
+// From integer
+impl<T> From<T> for Ratio<T>
 where
     T: Clone + Integer,
 {
-    type Output = Ratio<T>;
-    #[inline]
-    fn mul(self, rhs: T) -> Ratio<T> {
-        let gcd = self.denom.gcd(&rhs);
-        Ratio::new(self.numer * (rhs / gcd.clone()), self.denom / gcd)
+    fn from(x: T) -> Ratio<T> {
+        Ratio::from_integer(x)
     }
 }
 
-forward_all_binop!(impl Div, div);
-// (a/b) / (c/d) = (a/gcd_ac)*(d/gcd_bd) / ((c/gcd_ac)*(b/gcd_bd))
-impl<T> Div<Ratio<T>> for Ratio<T>
+// From pair (through the `new` constructor)
+impl<T> From<(T, T)> for Ratio<T>
 where
     T: Clone + Integer,
 {
-    type Output = Ratio<T>;
-
-    #[inline]
-    fn div(self, rhs: Ratio<T>) -> Ratio<T> {
-        let gcd_ac = self.numer.gcd(&rhs.numer);
-        let gcd_bd = self.denom.gcd(&rhs.denom);
-        Ratio::new(
-            self.numer / gcd_ac.clone() * (rhs.denom / gcd_bd.clone()),
-            self.denom / gcd_bd * (rhs.numer / gcd_ac),
-        )
+    /// Reduces the pair like `new`. Use `Ratio::from_pair_raw` to keep it as given.
+    ///
+    /// **Panics if the denominator is zero.**
+    fn from(pair: (T, T)) -> Ratio<T> {
+        Ratio::new(pair.0, pair.1)
     }
 }
-// (a/b) / (c/1) = (a*1) / (b*c) = a / (b*c)
-impl<T> Div<T> for Ratio<T>
-where
-    T: Clone + Integer,
-{
-    type Output = Ratio<T>;
 
+// Comparisons
+
+// Mathematically, comparing a/b and c/d is the same as comparing a*d and b*c, but it's very easy
+// for those multiplications to overflow fixed-size integers, so we need to take care.
+
+impl<T: Clone + Integer> Ord for Ratio<T> {
     #[inline]
-    fn div(self, rhs: T) -> Ratio<T> {
-        let gcd = self.numer.gcd(&rhs);
-        Ratio::new(self.numer / gcd.clone(), self.denom * (rhs / gcd))
-    }
-}
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        // With different signs, the signs alone decide the order
+        let self_sign = self.cmp_zero();
+        let other_sign = other.cmp_zero();
+        if self_sign != other_sign {
+            return self_sign.cmp(&other_sign);
+        }
 
-macro_rules! arith_impl {
-    (impl $imp:ident, $method:ident) => {
-        forward_all_binop!(impl $imp, $method);
-        // Abstracts a/b `op` c/d = (a*lcm/b `op` c*lcm/d)/lcm where lcm = lcm(b,d)
-        impl<T: Clone + Integer> $imp<Ratio<T>> for Ratio<T> {
-            type Output = Ratio<T>;
-            #[inline]
-            fn $method(self, rhs: Ratio<T>) -> Ratio<T> {
-                if self.denom == rhs.denom {
-                    return Ratio::new(self.numer.$method(rhs.numer), rhs.denom);
-                }
-                let lcm = self.denom.lcm(&rhs.denom);
-                let lhs_numer = self.numer * (lcm.clone() / self.denom);
-                let rhs_numer = rhs.numer * (lcm.clone() / rhs.denom);
-                Ratio::new(lhs_numer.$method(rhs_numer), lcm)
+        // With equal denominators, the numerators can be directly compared
+        if self.denom == other.denom {
+            let ord = self.numer.cmp(&other.numer);
+            return if self.denom < T::zero() {
+                ord.reverse()
+            } else {
+                ord
+            };
+        }
+
+        // With equal numerators, the denominators can be inversely compared
+        if self.numer == other.numer {
+            if self.numer.is_zero() {
+                return cmp::Ordering::Equal;
             }
+            let ord = self.denom.cmp(&other.denom);
+            return if self.numer < T::zero() {
+                ord
+            } else {
+                ord.reverse()
+            };
         }
-        // Abstracts the a/b `op` c/1 = (a*1 `op` b*c) / (b*1) = (a `op` b*c) / b pattern
-        impl<T: Clone + Integer> $imp<T> for Ratio<T> {
-            type Output = Ratio<T>;
-            #[inline]
-            fn $method(self, rhs: T) -> Ratio<T> {
-                Ratio::new(self.numer.$method(self.denom.clone() * rhs), self.denom)
+
+        // Unfortunately, we don't have CheckedMul to try.  That could sometimes avoid all the
+        // division below, or even always avoid it for BigInt and BigUint.
+        // FIXME- future breaking change to add Checked* to Integer?
+
+        // Compare as floored integers and remainders
+        let (self_int, self_rem) = self.numer.div_mod_floor(&self.denom);
+        let (other_int, other_rem) = other.numer.div_mod_floor(&other.denom);
+        match self_int.cmp(&other_int) {
+            cmp::Ordering::Greater => cmp::Ordering::Greater,
+            cmp::Ordering::Less => cmp::Ordering::Less,
+            cmp::Ordering::Equal => {
+                match (self_rem.is_zero(), other_rem.is_zero()) {
+                    (true, true) => cmp::Ordering::Equal,
+                    (true, false) => cmp::Ordering::Less,
+                    (false, true) => cmp::Ordering::Greater,
+                    (false, false) => {
+                        // Compare the reciprocals of the remaining fractions in reverse
+                        let self_recip = Ratio::new_raw(self.denom.clone(), self_rem);
+                        let other_recip = Ratio::new_raw(other.denom.clone(), other_rem);
+                        self_recip.cmp(&other_recip).reverse()
+                    }
+                }
             }
         }
-    };
+    }
 }
 
-arith_impl!(impl Add, add);
-arith_impl!(impl Sub, sub);
-arith_impl!(impl Rem, rem);
-
-// a/b * c/d = (a*c)/(b*d)
-impl<T> CheckedMul for Ratio<T>
-where
-    T: Clone + Integer + CheckedMul,
-{
+impl<T: Clone + Integer> PartialOrd for Ratio<T> {
     #[inline]
-    fn checked_mul(&self, rhs: &Ratio<T>) -> Option<Ratio<T>> {
-        let gcd_ad = self.numer.gcd(&rhs.denom);
-        let gcd_bc = self.denom.gcd(&rhs.numer);
-        Some(Ratio::new(
-            (self.numer.clone() / gcd_ad.clone())
-                .checked_mul(&(rhs.numer.clone() / gcd_bc.clone()))?,
-            (self.denom.clone() / gcd_bc).checked_mul(&(rhs.denom.clone() / gcd_ad))?,
-        ))
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
     }
 }
 
-// (a/b) / (c/d) = (a*d)/(b*c)
-impl<T> CheckedDiv for Ratio<T>
-where
-    T: Clone + Integer + CheckedMul,
-{
+impl<T: Clone + Integer> PartialEq for Ratio<T> {
     #[inline]
-    fn checked_div(&self, rhs: &Ratio<T>) -> Option<Ratio<T>> {
-        if rhs.is_zero() {
-            return None;
-        }
-        let (numer, denom) = if self.denom == rhs.denom {
-            (self.numer.clone(), rhs.numer.clone())
-        } else if self.numer == rhs.numer {
-            (rhs.denom.clone(), self.denom.clone())
-        } else {
-            let gcd_ac = self.numer.gcd(&rhs.numer);
-            let gcd_bd = self.denom.gcd(&rhs.denom);
-            (
-                (self.numer.clone() / gcd_ac.clone())
-                    .checked_mul(&(rhs.denom.clone() / gcd_bd.clone()))?,
-                (self.denom.clone() / gcd_bd).checked_mul(&(rhs.numer.clone() / gcd_ac))?,
-            )
-        };
-        // Manual `reduce()`, avoiding sharp edges
-        if denom.is_zero() {
-            None
-        } else if numer.is_zero() {
-            Some(Self::zero())
-        } else if numer == denom {
-            Some(Self::one())
-        } else {
-            let g = numer.gcd(&denom);
-            let numer = numer / g.clone();
-            let denom = denom / g;
-            let raw = if denom < T::zero() {
-                // We need to keep denom positive, but 2's-complement MIN may
-                // overflow negation -- instead we can check multiplying -1.
-                let n1 = T::zero() - T::one();
-                Ratio::new_raw(numer.checked_mul(&n1)?, denom.checked_mul(&n1)?)
-            } else {
-                Ratio::new_raw(numer, denom)
-            };
-            Some(raw)
-        }
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
     }
 }
 
-// As arith_impl! but for Checked{Add,Sub} traits
-macro_rules! checked_arith_impl {
-    (impl $imp:ident, $method:ident) => {
-        impl<T: Clone + Integer + CheckedMul + $imp> $imp for Ratio<T> {
-            #[inline]
-            fn $method(&self, rhs: &Ratio<T>) -> Option<Ratio<T>> {
-                let gcd = self.denom.clone().gcd(&rhs.denom);
-                let lcm = (self.denom.clone() / gcd.clone()).checked_mul(&rhs.denom)?;
-                let lhs_numer = (lcm.clone() / self.denom.clone()).checked_mul(&self.numer)?;
-                let rhs_numer = (lcm.clone() / rhs.denom.clone()).checked_mul(&rhs.numer)?;
-                Some(Ratio::new(lhs_numer.$method(&rhs_numer)?, lcm))
-            }
-        }
-    };
-}
-
-// a/b + c/d = (lcm/b*a + lcm/d*c)/lcm, where lcm = lcm(b,d)
-checked_arith_impl!(impl CheckedAdd, checked_add);
-
-// a/b - c/d = (lcm/b*a - lcm/d*c)/lcm, where lcm = lcm(b,d)
-checked_arith_impl!(impl CheckedSub, checked_sub);
+impl<T: Clone + Integer> Eq for Ratio<T> {}
 
-impl<T> Neg for Ratio<T>
-where
-    T: Clone + Integer + Neg<Output = T>,
-{
-    type Output = Ratio<T>;
+// NB: We can't just `#[derive(Hash)]`, because it needs to agree
+// with `Eq` even for non-reduced ratios.
+impl<T: Clone + Integer + Hash> Hash for Ratio<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        recurse(&self.numer, &self.denom, state);
 
-    #[inline]
-    fn neg(self) -> Ratio<T> {
-        Ratio::new_raw(-self.numer, self.denom)
+        fn recurse<T: Integer + Hash, H: Hasher>(numer: &T, denom: &T, state: &mut H) {
+            if !denom.is_zero() {
+                let (int, rem) = numer.div_mod_floor(denom);
+                int.hash(state);
+                recurse(denom, &rem, state);
+            } else {
+                denom.hash(state);
+            }
+        }
     }
 }
 
-impl<'a, T> Neg for &'a Ratio<T>
-where
-    T: Clone + Integer + Neg<Output = T>,
-{
-    type Output = Ratio<T>;
+mod iter_sum_product {
+    use crate::Ratio;
+    use core::iter::{Product, Sum};
+    use num_integer::Integer;
+    use num_traits::{CheckedAdd, CheckedMul, One, Zero};
 
-    #[inline]
-    fn neg(self) -> Ratio<T> {
-        -self.clone()
+    impl<T: Integer + Clone> Sum for Ratio<T> {
+        fn sum<I>(iter: I) -> Self
+        where
+            I: Iterator<Item = Ratio<T>>,
+        {
+            iter.fold(Self::zero(), |sum, num| sum + num)
+        }
     }
-}
 
-impl<T> Inv for Ratio<T>
-where
-    T: Clone + Integer,
-{
-    type Output = Ratio<T>;
+    impl<'a, T: Integer + Clone> Sum<&'a Ratio<T>> for Ratio<T> {
+        fn sum<I>(iter: I) -> Self
+        where
+            I: Iterator<Item = &'a Ratio<T>>,
+        {
+            iter.fold(Self::zero(), |sum, num| sum + num)
+        }
+    }
 
-    #[inline]
-    fn inv(self) -> Ratio<T> {
-        self.recip()
+    /// Collecting ratios sums them, the same as `Sum`.
+    impl<T: Integer + Clone> FromIterator<Ratio<T>> for Ratio<T> {
+        fn from_iter<I>(iter: I) -> Self
+        where
+            I: IntoIterator<Item = Ratio<T>>,
+        {
+            iter.into_iter().sum()
+        }
     }
-}
 
-impl<'a, T> Inv for &'a Ratio<T>
-where
-    T: Clone + Integer,
-{
-    type Output = Ratio<T>;
+    impl<T: Integer + Clone> Product for Ratio<T> {
+        fn product<I>(iter: I) -> Self
+        where
+            I: Iterator<Item = Ratio<T>>,
+        {
+            iter.fold(Self::one(), |prod, num| prod * num)
+        }
+    }
 
-    #[inline]
-    fn inv(self) -> Ratio<T> {
-        self.recip()
+    impl<'a, T: Integer + Clone> Product<&'a Ratio<T>> for Ratio<T> {
+        fn product<I>(iter: I) -> Self
+        where
+            I: Iterator<Item = &'a Ratio<T>>,
+        {
+            iter.fold(Self::one(), |prod, num| prod * num)
+        }
     }
-}
 
-// Constants
-impl<T: ConstZero + ConstOne> Ratio<T> {
-    /// A constant `Ratio` 0/1.
-    pub const ZERO: Self = Self::new_raw(T::ZERO, T::ONE);
-}
+    impl<T: Integer + Clone> Ratio<T> {
+        /// Multiplies a slice of ratios by splitting it in halves recursively, reducing each
+        /// partial product.
+        ///
+        /// The result is the same as `Product`, but combining balanced halves keeps the
+        /// intermediate values smaller than a left fold, which is much faster for big rationals.
+        pub fn product_balanced(ratios: &[Ratio<T>]) -> Self {
+            match ratios {
+                [] => Self::one(),
+                [r] => r.clone(),
+                _ => {
+                    let (left, right) = ratios.split_at(ratios.len() / 2);
+                    Self::product_balanced(left) * Self::product_balanced(right)
+                }
+            }
+        }
 
-impl<T: Clone + Integer + ConstZero + ConstOne> ConstZero for Ratio<T> {
-    const ZERO: Self = Self::ZERO;
-}
+        /// Sums a slice of ratios by splitting it in halves recursively, reducing each partial
+        /// sum.
+        ///
+        /// The result is the same as `Sum`, but like `product_balanced`, combining balanced
+        /// halves keeps the intermediate values smaller than a left fold, which is much faster
+        /// for big rationals.
+        pub fn sum_pairwise(ratios: &[Ratio<T>]) -> Self {
+            match ratios {
+                [] => Self::zero(),
+                [r] => r.clone(),
+                _ => {
+                    let (left, right) = ratios.split_at(ratios.len() / 2);
+                    Self::sum_pairwise(left) + Self::sum_pairwise(right)
+                }
+            }
+        }
 
-impl<T: Clone + Integer> Zero for Ratio<T> {
-    #[inline]
-    fn zero() -> Ratio<T> {
-        Ratio::new_raw(Zero::zero(), One::one())
+        /// Sums a slice of ratios over the least common multiple of their denominators,
+        /// reducing only once at the end.
+        ///
+        /// The result is the same as `Sum`, without computing a gcd for every addition, which
+        /// is substantially faster for big rationals. For fixed-size types, the common
+        /// denominator may overflow even when the sum would not.
+        pub fn sum_common_denom(ratios: &[Ratio<T>]) -> Self {
+            let denom = ratios.iter().fold(T::one(), |lcm, r| lcm.lcm(&r.denom));
+            let numer = ratios.iter().fold(T::zero(), |sum, r| {
+                sum + r.numer.clone() * (denom.clone() / r.denom.clone())
+            });
+            Ratio::new(numer, denom)
+        }
     }
 
-    #[inline]
-    fn is_zero(&self) -> bool {
-        self.numer.is_zero()
-    }
+    impl<T: Integer + Clone + CheckedAdd + CheckedMul> Ratio<T> {
+        /// Sums the items, returning `None` if any partial sum overflows.
+        pub fn checked_sum<I>(iter: I) -> Option<Self>
+        where
+            I: IntoIterator<Item = Ratio<T>>,
+        {
+            iter.into_iter()
+                .try_fold(Self::zero(), |sum, num| sum.checked_add(&num))
+        }
 
-    #[inline]
-    fn set_zero(&mut self) {
-        self.numer.set_zero();
-        self.denom.set_one();
+        /// Sums borrowed items, returning `None` if any partial sum overflows.
+        ///
+        /// This avoids cloning each item, which matters for `Ratio<BigInt>`.
+        pub fn checked_sum_ref<'a, I>(iter: I) -> Option<Self>
+        where
+            I: IntoIterator<Item = &'a Ratio<T>>,
+            T: 'a,
+        {
+            iter.into_iter()
+                .try_fold(Self::zero(), |sum, num| sum.checked_add(num))
+        }
     }
-}
-
-impl<T: ConstOne> Ratio<T> {
-    /// A constant `Ratio` 1/1.
-    pub const ONE: Self = Self::new_raw(T::ONE, T::ONE);
-}
 
-impl<T: Clone + Integer + ConstOne> ConstOne for Ratio<T> {
-    const ONE: Self = Self::ONE;
-}
+    /// Checked versions of `Iterator::sum` and `Iterator::product` for iterators of ratios.
+    pub trait CheckedSumExt<T>: Iterator<Item = Ratio<T>> + Sized {
+        /// Sums the items, returning `None` if any partial sum overflows.
+        fn collect_checked_sum(self) -> Option<Ratio<T>>;
 
-impl<T: Clone + Integer> One for Ratio<T> {
-    #[inline]
-    fn one() -> Ratio<T> {
-        Ratio::new_raw(One::one(), One::one())
+        /// Multiplies the items, returning `None` if any partial product overflows.
+        fn collect_checked_product(self) -> Option<Ratio<T>>;
     }
 
-    #[inline]
-    fn is_one(&self) -> bool {
-        self.numer == self.denom
-    }
+    impl<T, I> CheckedSumExt<T> for I
+    where
+        T: Integer + Clone + CheckedAdd + CheckedMul,
+        I: Iterator<Item = Ratio<T>>,
+    {
+        fn collect_checked_sum(self) -> Option<Ratio<T>> {
+            Ratio::checked_sum(self)
+        }
 
-    #[inline]
-    fn set_one(&mut self) {
-        self.numer.set_one();
-        self.denom.set_one();
+        fn collect_checked_product(mut self) -> Option<Ratio<T>> {
+            self.try_fold(Ratio::one(), |prod, num| prod.checked_mul(&num))
+        }
     }
 }
 
-impl<T: Clone + Integer> Num for Ratio<T> {
-    type FromStrRadixErr = ParseRatioError;
+mod opassign {
+    use core::ops::{AddAssign, DivAssign, MulAssign, RemAssign, SubAssign};
 
-    /// Parses `numer/denom` where the numbers are in base `radix`.
-    fn from_str_radix(s: &str, radix: u32) -> Result<Ratio<T>, ParseRatioError> {
-        if s.splitn(2, '/').count() == 2 {
-            let mut parts = s.splitn(2, '/').map(|ss| {
-                T::from_str_radix(ss, radix).map_err(|_| ParseRatioError {
-                    kind: RatioErrorKind::ParseError,
-                })
-            });
-            let numer: T = parts.next().unwrap()?;
-            let denom: T = parts.next().unwrap()?;
-            if denom.is_zero() {
-                Err(ParseRatioError {
-                    kind: RatioErrorKind::ZeroDenominator,
-                })
+    use crate::Ratio;
+    use num_integer::Integer;
+    use num_traits::{NumAssign, Zero};
+
+    impl<T: Clone + Integer + NumAssign> AddAssign for Ratio<T> {
+        fn add_assign(&mut self, other: Ratio<T>) {
+            if self.denom == other.denom {
+                self.numer += other.numer
             } else {
-                Ok(Ratio::new(numer, denom))
+                let g = self.denom.gcd(&other.denom);
+                let lhs_factor = self.denom.clone() / g.clone();
+                let lhs_numer = self.numer.clone() * (other.denom.clone() / g.clone());
+                let rhs_numer = other.numer * lhs_factor.clone();
+                let numer = lhs_numer + rhs_numer;
+                let g2 = numer.gcd(&g);
+                self.numer = numer / g2.clone();
+                self.denom = lhs_factor * (other.denom / g2);
             }
-        } else {
-            Err(ParseRatioError {
-                kind: RatioErrorKind::ParseError,
-            })
+            self.reduce();
         }
     }
-}
 
-impl<T: Clone + Integer + Signed> Signed for Ratio<T> {
-    #[inline]
-    fn abs(&self) -> Ratio<T> {
-        if self.is_negative() {
-            -self.clone()
-        } else {
-            self.clone()
+    // (a/b) / (c/d) = (a/gcd_ac)*(d/gcd_bd) / ((c/gcd_ac)*(b/gcd_bd))
+    impl<T: Clone + Integer + NumAssign> DivAssign for Ratio<T> {
+        fn div_assign(&mut self, other: Ratio<T>) {
+            if other.is_zero() {
+                panic!("division by zero rational");
+            }
+            let gcd_ac = self.numer.gcd(&other.numer);
+            let gcd_bd = self.denom.gcd(&other.denom);
+            self.numer /= gcd_ac.clone();
+            self.numer *= other.denom / gcd_bd.clone();
+            self.denom /= gcd_bd;
+            self.denom *= other.numer / gcd_ac;
+            self.reduce(); // TODO: remove this line. see #8.
         }
     }
 
-    #[inline]
-    fn abs_sub(&self, other: &Ratio<T>) -> Ratio<T> {
-        if *self <= *other {
-            Zero::zero()
-        } else {
-            self - other
+    // a/b * c/d = (a/gcd_ad)*(c/gcd_bc) / ((d/gcd_ad)*(b/gcd_bc))
+    impl<T: Clone + Integer + NumAssign> MulAssign for Ratio<T> {
+        fn mul_assign(&mut self, other: Ratio<T>) {
+            let gcd_ad = self.numer.gcd(&other.denom);
+            let gcd_bc = self.denom.gcd(&other.numer);
+            self.numer /= gcd_ad.clone();
+            self.numer *= other.numer / gcd_bc.clone();
+            self.denom /= gcd_bc;
+            self.denom *= other.denom / gcd_ad;
+            self.reduce(); // TODO: remove this line. see #8.
         }
     }
 
-    #[inline]
-    fn signum(&self) -> Ratio<T> {
-        if self.is_positive() {
-            Self::one()
-        } else if self.is_zero() {
-            Self::zero()
-        } else {
-            -Self::one()
+    impl<T: Clone + Integer + NumAssign> RemAssign for Ratio<T> {
+        fn rem_assign(&mut self, other: Ratio<T>) {
+            if other.is_zero() {
+                panic!("division by zero rational");
+            }
+            if self.denom == other.denom {
+                self.numer %= other.numer
+            } else {
+                let lcm = self.denom.lcm(&other.denom);
+                let lhs_numer = self.numer.clone() * (lcm.clone() / self.denom.clone());
+                let rhs_numer = other.numer * (lcm.clone() / other.denom);
+                self.numer = lhs_numer % rhs_numer;
+                self.denom = lcm;
+            }
+            self.reduce();
         }
     }
 
-    #[inline]
-    fn is_positive(&self) -> bool {
-        (self.numer.is_positive() && self.denom.is_positive())
-            || (self.numer.is_negative() && self.denom.is_negative())
+    impl<T: Clone + Integer + NumAssign> SubAssign for Ratio<T> {
+        fn sub_assign(&mut self, other: Ratio<T>) {
+            if self.denom == other.denom {
+                self.numer -= other.numer
+            } else {
+                let g = self.denom.gcd(&other.denom);
+                let lhs_factor = self.denom.clone() / g.clone();
+                let lhs_numer = self.numer.clone() * (other.denom.clone() / g.clone());
+                let rhs_numer = other.numer * lhs_factor.clone();
+                let numer = lhs_numer - rhs_numer;
+                let g2 = numer.gcd(&g);
+                self.numer = numer / g2.clone();
+                self.denom = lhs_factor * (other.denom / g2);
+            }
+            self.reduce();
+        }
     }
 
-    #[inline]
-    fn is_negative(&self) -> bool {
-        (self.numer.is_negative() && self.denom.is_positive())
-            || (self.numer.is_positive() && self.denom.is_negative())
+    // a/b + c/1 = (a*1 + b*c) / (b*1) = (a + b*c) / b
+    impl<T: Clone + Integer + NumAssign> AddAssign<T> for Ratio<T> {
+        fn add_assign(&mut self, other: T) {
+            self.numer += self.denom.clone() * other;
+            self.reduce();
+        }
     }
-}
 
-// String conversions
-macro_rules! impl_formatting {
-    ($fmt_trait:ident, $prefix:expr, $fmt_str:expr, $fmt_alt:expr) => {
-        impl<T: $fmt_trait + Clone + Integer> $fmt_trait for Ratio<T> {
-            #[cfg(feature = "std")]
-            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-                let pre_pad = if self.denom.is_one() {
-                    format!($fmt_str, self.numer)
-                } else {
-                    if f.alternate() {
-                        format!(concat!($fmt_str, "/", $fmt_alt), self.numer, self.denom)
-                    } else {
-                        format!(concat!($fmt_str, "/", $fmt_str), self.numer, self.denom)
-                    }
-                };
-                if let Some(pre_pad) = pre_pad.strip_prefix("-") {
-                    f.pad_integral(false, $prefix, pre_pad)
-                } else {
-                    f.pad_integral(true, $prefix, &pre_pad)
-                }
-            }
-            #[cfg(not(feature = "std"))]
-            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-                let plus = if f.sign_plus() && self.numer >= T::zero() {
-                    "+"
-                } else {
-                    ""
-                };
-                if self.denom.is_one() {
-                    if f.alternate() {
-                        write!(f, concat!("{}", $fmt_alt), plus, self.numer)
-                    } else {
-                        write!(f, concat!("{}", $fmt_str), plus, self.numer)
-                    }
-                } else {
-                    if f.alternate() {
-                        write!(
-                            f,
-                            concat!("{}", $fmt_alt, "/", $fmt_alt),
-                            plus, self.numer, self.denom
-                        )
-                    } else {
-                        write!(
-                            f,
-                            concat!("{}", $fmt_str, "/", $fmt_str),
-                            plus, self.numer, self.denom
-                        )
-                    }
-                }
+    impl<T: Clone + Integer + NumAssign> DivAssign<T> for Ratio<T> {
+        fn div_assign(&mut self, other: T) {
+            if other.is_zero() {
+                panic!("division by zero rational");
             }
+            let gcd = self.numer.gcd(&other);
+            self.numer /= gcd.clone();
+            self.denom *= other / gcd;
+            self.reduce(); // TODO: remove this line. see #8.
         }
-    };
-}
-
-impl_formatting!(Display, "", "{}", "{:#}");
-impl_formatting!(Octal, "0o", "{:o}", "{:#o}");
-impl_formatting!(Binary, "0b", "{:b}", "{:#b}");
-impl_formatting!(LowerHex, "0x", "{:x}", "{:#x}");
-impl_formatting!(UpperHex, "0x", "{:X}", "{:#X}");
-impl_formatting!(LowerExp, "", "{:e}", "{:#e}");
-impl_formatting!(UpperExp, "", "{:E}", "{:#E}");
-
-impl<T: FromStr + Clone + Integer> FromStr for Ratio<T> {
-    type Err = ParseRatioError;
-
-    /// Parses `numer/denom` or just `numer`.
-    fn from_str(s: &str) -> Result<Ratio<T>, ParseRatioError> {
-        let mut split = s.splitn(2, '/');
-
-        let n = split.next().ok_or(ParseRatioError {
-            kind: RatioErrorKind::ParseError,
-        })?;
-        let num = FromStr::from_str(n).map_err(|_| ParseRatioError {
-            kind: RatioErrorKind::ParseError,
-        })?;
-
-        let d = split.next().unwrap_or("1");
-        let den = FromStr::from_str(d).map_err(|_| ParseRatioError {
-            kind: RatioErrorKind::ParseError,
-        })?;
+    }
 
-        if Zero::is_zero(&den) {
-            Err(ParseRatioError {
-                kind: RatioErrorKind::ZeroDenominator,
-            })
-        } else {
-            Ok(Ratio::new(num, den))
+    impl<T: Clone + Integer + NumAssign> MulAssign<T> for Ratio<T> {
+        fn mul_assign(&mut self, other: T) {
+            let gcd = self.denom.gcd(&other);
+            self.denom /= gcd.clone();
+            self.numer *= other / gcd;
+            self.reduce(); // TODO: remove this line. see #8.
         }
     }
-}
 
-impl<T> From<Ratio<T>> for (T, T) {
-    fn from(val: Ratio<T>) -> Self {
-        (val.numer, val.denom)
+    // a/b % c/1 = (a*1 % b*c) / (b*1) = (a % b*c) / b
+    impl<T: Clone + Integer + NumAssign> RemAssign<T> for Ratio<T> {
+        fn rem_assign(&mut self, other: T) {
+            if other.is_zero() {
+                panic!("division by zero rational");
+            }
+            self.numer %= self.denom.clone() * other;
+            self.reduce();
+        }
     }
-}
 
-#[cfg(feature = "serde")]
-impl<T> serde::Serialize for Ratio<T>
-where
-    T: serde::Serialize + Clone + Integer + PartialOrd,
-{
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-    where
-        S: serde::Serializer,
-    {
-        (self.numer(), self.denom()).serialize(serializer)
+    // a/b - c/1 = (a*1 - b*c) / (b*1) = (a - b*c) / b
+    impl<T: Clone + Integer + NumAssign> SubAssign<T> for Ratio<T> {
+        fn sub_assign(&mut self, other: T) {
+            self.numer -= self.denom.clone() * other;
+            self.reduce();
+        }
     }
-}
 
-#[cfg(feature = "serde")]
-impl<'de, T> serde::Deserialize<'de> for Ratio<T>
-where
-    T: serde::Deserialize<'de> + Clone + Integer + PartialOrd,
-{
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        use serde::de::Error;
-        use serde::de::Unexpected;
-        let (numer, denom): (T, T) = serde::Deserialize::deserialize(deserializer)?;
-        if denom.is_zero() {
-            Err(Error::invalid_value(
-                Unexpected::Signed(0),
-                &"a ratio with non-zero denominator",
-            ))
-        } else {
-            Ok(Ratio::new_raw(numer, denom))
-        }
+    macro_rules! forward_op_assign {
+        (impl $imp:ident, $method:ident) => {
+            impl<'a, T: Clone + Integer + NumAssign> $imp<&'a Ratio<T>> for Ratio<T> {
+                #[inline]
+                fn $method(&mut self, other: &Ratio<T>) {
+                    self.$method(other.clone())
+                }
+            }
+            impl<'a, T: Clone + Integer + NumAssign> $imp<&'a T> for Ratio<T> {
+                #[inline]
+                fn $method(&mut self, other: &T) {
+                    self.$method(other.clone())
+                }
+            }
+        };
     }
-}
 
-// FIXME: Bubble up specific errors
-#[derive(Copy, Clone, Debug, PartialEq)]
-pub struct ParseRatioError {
-    kind: RatioErrorKind,
+    forward_op_assign!(impl AddAssign, add_assign);
+    forward_op_assign!(impl DivAssign, div_assign);
+    forward_op_assign!(impl MulAssign, mul_assign);
+    forward_op_assign!(impl RemAssign, rem_assign);
+    forward_op_assign!(impl SubAssign, sub_assign);
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
-enum RatioErrorKind {
-    ParseError,
-    ZeroDenominator,
-}
+macro_rules! forward_ref_ref_binop {
+    (impl $imp:ident, $method:ident) => {
+        impl<'a, 'b, T: Clone + Integer> $imp<&'b Ratio<T>> for &'a Ratio<T> {
+            type Output = Ratio<T>;
 
-impl fmt::Display for ParseRatioError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.kind.description().fmt(f)
-    }
-}
+            #[inline]
+            fn $method(self, other: &'b Ratio<T>) -> Ratio<T> {
+                self.clone().$method(other.clone())
+            }
+        }
+        impl<'a, 'b, T: Clone + Integer> $imp<&'b T> for &'a Ratio<T> {
+            type Output = Ratio<T>;
 
-#[cfg(feature = "std")]
-impl Error for ParseRatioError {
+            #[inline]
+            fn $method(self, other: &'b T) -> Ratio<T> {
+                self.clone().$method(other.clone())
+            }
+        }
+    };
+}
+
+macro_rules! forward_ref_val_binop {
+    (impl $imp:ident, $method:ident) => {
+        impl<'a, T> $imp<Ratio<T>> for &'a Ratio<T>
+        where
+            T: Clone + Integer,
+        {
+            type Output = Ratio<T>;
+
+            #[inline]
+            fn $method(self, other: Ratio<T>) -> Ratio<T> {
+                self.clone().$method(other)
+            }
+        }
+        impl<'a, T> $imp<T> for &'a Ratio<T>
+        where
+            T: Clone + Integer,
+        {
+            type Output = Ratio<T>;
+
+            #[inline]
+            fn $method(self, other: T) -> Ratio<T> {
+                self.clone().$method(other)
+            }
+        }
+    };
+}
+
+macro_rules! forward_val_ref_binop {
+    (impl $imp:ident, $method:ident) => {
+        impl<'a, T> $imp<&'a Ratio<T>> for Ratio<T>
+        where
+            T: Clone + Integer,
+        {
+            type Output = Ratio<T>;
+
+            #[inline]
+            fn $method(self, other: &Ratio<T>) -> Ratio<T> {
+                self.$method(other.clone())
+            }
+        }
+        impl<'a, T> $imp<&'a T> for Ratio<T>
+        where
+            T: Clone + Integer,
+        {
+            type Output = Ratio<T>;
+
+            #[inline]
+            fn $method(self, other: &T) -> Ratio<T> {
+                self.$method(other.clone())
+            }
+        }
+    };
+}
+
+macro_rules! forward_all_binop {
+    (impl $imp:ident, $method:ident) => {
+        forward_ref_ref_binop!(impl $imp, $method);
+        forward_ref_val_binop!(impl $imp, $method);
+        forward_val_ref_binop!(impl $imp, $method);
+    };
+}
+
+// Arithmetic
+forward_all_binop!(impl Mul, mul);
+// a/b * c/d = (a/gcd_ad)*(c/gcd_bc) / ((d/gcd_ad)*(b/gcd_bc))
+impl<T> Mul<Ratio<T>> for Ratio<T>
+where
+    T: Clone + Integer,
+{
+    type Output = Ratio<T>;
+    #[inline]
+    fn mul(self, rhs: Ratio<T>) -> Ratio<T> {
+        let gcd_ad = self.numer.gcd(&rhs.denom);
+        let gcd_bc = self.denom.gcd(&rhs.numer);
+        Ratio::new(
+            self.numer / gcd_ad.clone() * (rhs.numer / gcd_bc.clone()),
+            self.denom / gcd_bc * (rhs.denom / gcd_ad),
+        )
+    }
+}
+// a/b * c/1 = (a*c) / (b*1) = (a*c) / b
+impl<T> Mul<T> for Ratio<T>
+where
+    T: Clone + Integer,
+{
+    type Output = Ratio<T>;
+    #[inline]
+    fn mul(self, rhs: T) -> Ratio<T> {
+        let gcd = self.denom.gcd(&rhs);
+        Ratio::new(self.numer * (rhs / gcd.clone()), self.denom / gcd)
+    }
+}
+
+forward_all_binop!(impl Div, div);
+// (a/b) / (c/d) = (a/gcd_ac)*(d/gcd_bd) / ((c/gcd_ac)*(b/gcd_bd))
+impl<T> Div<Ratio<T>> for Ratio<T>
+where
+    T: Clone + Integer,
+{
+    type Output = Ratio<T>;
+
+    #[inline]
+    fn div(self, rhs: Ratio<T>) -> Ratio<T> {
+        if rhs.is_zero() {
+            panic!("division by zero rational");
+        }
+        let gcd_ac = self.numer.gcd(&rhs.numer);
+        let gcd_bd = self.denom.gcd(&rhs.denom);
+        Ratio::new(
+            self.numer / gcd_ac.clone() * (rhs.denom / gcd_bd.clone()),
+            self.denom / gcd_bd * (rhs.numer / gcd_ac),
+        )
+    }
+}
+// (a/b) / (c/1) = (a*1) / (b*c) = a / (b*c)
+impl<T> Div<T> for Ratio<T>
+where
+    T: Clone + Integer,
+{
+    type Output = Ratio<T>;
+
+    #[inline]
+    fn div(self, rhs: T) -> Ratio<T> {
+        if rhs.is_zero() {
+            panic!("division by zero rational");
+        }
+        let gcd = self.numer.gcd(&rhs);
+        Ratio::new(self.numer / gcd.clone(), self.denom * (rhs / gcd))
+    }
+}
+
+macro_rules! arith_impl {
+    (impl $imp:ident, $method:ident) => {
+        forward_all_binop!(impl $imp, $method);
+        // Abstracts a/b `op` c/d = t/lcm(b,d), where g = gcd(b,d) and t = a*(d/g) `op` c*(b/g).
+        // Any factor that t shares with the lcm is also in g, so dividing it out of t and d
+        // before forming the denominator keeps it from overflowing when the result fits.
+        impl<T: Clone + Integer> $imp<Ratio<T>> for Ratio<T> {
+            type Output = Ratio<T>;
+            #[inline]
+            fn $method(self, rhs: Ratio<T>) -> Ratio<T> {
+                if self.denom == rhs.denom {
+                    return Ratio::new(self.numer.$method(rhs.numer), rhs.denom);
+                }
+                let g = self.denom.gcd(&rhs.denom);
+                let lhs_factor = self.denom / g.clone();
+                let numer = (self.numer * (rhs.denom.clone() / g.clone()))
+                    .$method(rhs.numer * lhs_factor.clone());
+                let g2 = numer.gcd(&g);
+                Ratio::new(numer / g2.clone(), lhs_factor * (rhs.denom / g2))
+            }
+        }
+        // Abstracts the a/b `op` c/1 = (a*1 `op` b*c) / (b*1) = (a `op` b*c) / b pattern
+        impl<T: Clone + Integer> $imp<T> for Ratio<T> {
+            type Output = Ratio<T>;
+            #[inline]
+            fn $method(self, rhs: T) -> Ratio<T> {
+                Ratio::new(self.numer.$method(self.denom.clone() * rhs), self.denom)
+            }
+        }
+    };
+}
+
+arith_impl!(impl Add, add);
+arith_impl!(impl Sub, sub);
+
+// As arith_impl! but checking for a zero divisor first
+forward_all_binop!(impl Rem, rem);
+impl<T: Clone + Integer> Rem<Ratio<T>> for Ratio<T> {
+    type Output = Ratio<T>;
+    #[inline]
+    fn rem(self, rhs: Ratio<T>) -> Ratio<T> {
+        if rhs.is_zero() {
+            panic!("division by zero rational");
+        }
+        if self.denom == rhs.denom {
+            return Ratio::new(self.numer % rhs.numer, rhs.denom);
+        }
+        let lcm = self.denom.lcm(&rhs.denom);
+        let lhs_numer = self.numer * (lcm.clone() / self.denom);
+        let rhs_numer = rhs.numer * (lcm.clone() / rhs.denom);
+        Ratio::new(lhs_numer % rhs_numer, lcm)
+    }
+}
+impl<T: Clone + Integer> Rem<T> for Ratio<T> {
+    type Output = Ratio<T>;
+    #[inline]
+    fn rem(self, rhs: T) -> Ratio<T> {
+        if rhs.is_zero() {
+            panic!("division by zero rational");
+        }
+        Ratio::new(self.numer % (self.denom.clone() * rhs), self.denom)
+    }
+}
+
+// a/b * c/d = (a*c)/(b*d)
+impl<T> CheckedMul for Ratio<T>
+where
+    T: Clone + Integer + CheckedMul,
+{
+    #[inline]
+    fn checked_mul(&self, rhs: &Ratio<T>) -> Option<Ratio<T>> {
+        let gcd_ad = self.numer.gcd(&rhs.denom);
+        let gcd_bc = self.denom.gcd(&rhs.numer);
+        Some(Ratio::new(
+            (self.numer.clone() / gcd_ad.clone())
+                .checked_mul(&(rhs.numer.clone() / gcd_bc.clone()))?,
+            (self.denom.clone() / gcd_bc).checked_mul(&(rhs.denom.clone() / gcd_ad))?,
+        ))
+    }
+}
+
+impl<T: Clone + Integer + CheckedMul> Ratio<T> {
+    /// Creates a `Ratio` representing `mantissa * 10^exp10`, reduced, or `None` if that
+    /// overflows.
+    pub fn checked_from_scientific_parts(mut mantissa: T, mut exp10: i32) -> Option<Ratio<T>> {
+        if mantissa.is_zero() {
+            return Some(Ratio::zero());
+        }
+        let ten = ten::<T>();
+        while exp10 < 0 && mantissa.is_multiple_of(&ten) {
+            mantissa = mantissa / ten.clone();
+            exp10 += 1;
+        }
+        let scale = num_traits::checked_pow(ten, exp10.unsigned_abs() as usize)?;
+        if exp10 < 0 {
+            Some(Ratio::new(mantissa, scale))
+        } else {
+            Some(Ratio::new(mantissa.checked_mul(&scale)?, One::one()))
+        }
+    }
+
+    /// Creates a reduced `Ratio` like `new`, but returns `None` instead of panicking if `denom`
+    /// is zero, or if making the denominator positive overflows, as for `(i64::MIN, -1)`.
+    pub fn checked_from_pair(numer: T, denom: T) -> Option<Ratio<T>> {
+        if denom.is_zero() {
+            return None;
+        }
+        if numer.is_zero() || numer == denom {
+            let mut r = Ratio::new_raw(numer, denom);
+            r.reduce();
+            return Some(r);
+        }
+        let g = numer.gcd(&denom);
+        let (numer, denom) = (numer / g.clone(), denom / g);
+        if denom < T::zero() {
+            // 2's-complement MIN may overflow negation -- instead we can check multiplying -1.
+            let n1 = T::zero() - T::one();
+            Some(Ratio::new_raw(
+                numer.checked_mul(&n1)?,
+                denom.checked_mul(&n1)?,
+            ))
+        } else {
+            Some(Ratio::new_raw(numer, denom))
+        }
+    }
+
+    /// Divides by the integer `rhs`, returning `None` if `rhs` is zero or the result
+    /// overflows.
+    #[inline]
+    pub fn checked_div_by_int(&self, rhs: &T) -> Option<Ratio<T>> {
+        self.checked_div(&Ratio::from_integer(rhs.clone()))
+    }
+
+    /// Same as `checked_add`.
+    #[inline]
+    pub fn checked_add_lcm(&self, rhs: &Ratio<T>) -> Option<Ratio<T>>
+    where
+        T: CheckedAdd,
+    {
+        self.checked_add(rhs)
+    }
+
+    /// Adds `rhs` like `CheckedAdd`, also returning whether that overflowed, like the
+    /// `overflowing_add` of primitive integers.
+    ///
+    /// The flag is set exactly when `checked_add` returns `None`. The sum is then left
+    /// unreduced, as `a*(d/g) + c*(b/g)` over `lcm(b, d) = (b/g)*d` with `g = gcd(b, d)`, each
+    /// part wrapped in two's complement like the primitive `wrapping_mul` and `wrapping_add`.
+    /// So the wrapped denominator may be negative, as for `1i8/9 + 1/25`, which gives
+    /// `34/-31`. For positive denominators it is never zero.
+    pub fn overflowing_add(&self, rhs: &Ratio<T>) -> (Ratio<T>, bool)
+    where
+        T: CheckedAdd + WrappingAdd + WrappingMul,
+    {
+        if let Some(sum) = self.checked_add(rhs) {
+            return (sum, false);
+        }
+        // The factors are computed before anything wraps. The wrapped lcm keeps the factors of
+        // two of the exact one, which are no more than the larger denominator has, so it can't
+        // be zero.
+        let gcd = self.denom.gcd(&rhs.denom);
+        let lhs_factor = self.denom.clone() / gcd.clone();
+        let rhs_factor = rhs.denom.clone() / gcd;
+        let lcm = lhs_factor.wrapping_mul(&rhs.denom);
+        let lhs_numer = self.numer.wrapping_mul(&rhs_factor);
+        let rhs_numer = rhs.numer.wrapping_mul(&lhs_factor);
+        (
+            Ratio::new_raw(lhs_numer.wrapping_add(&rhs_numer), lcm),
+            true,
+        )
+    }
+
+    /// Subtracts the integer `rhs`, returning `None` if the result overflows.
+    #[inline]
+    pub fn checked_sub_int(&self, rhs: &T) -> Option<Ratio<T>>
+    where
+        T: CheckedSub,
+    {
+        // (a - b*c) / b is already reduced, since gcd(a - b*c, b) = gcd(a, b).
+        let offset = self.denom.checked_mul(rhs)?;
+        Some(Ratio::new_raw(
+            self.numer.checked_sub(&offset)?,
+            self.denom.clone(),
+        ))
+    }
+
+    /// Returns `self * 10^scale` rounded to the nearest integer, with half-way cases rounded
+    /// away from zero, or `None` if that overflows.
+    pub fn checked_to_scaled_integer(&self, scale: u32) -> Option<T>
+    where
+        T: CheckedAdd + CheckedSub,
+    {
+        let scale = num_traits::checked_pow(ten::<T>(), scale as usize)?;
+        let gcd = self.denom.gcd(&scale);
+        let scaled = Ratio::new_raw(
+            self.numer.checked_mul(&(scale / gcd.clone()))?,
+            self.denom.clone() / gcd,
+        );
+
+        let trunc = scaled.to_integer();
+        let mut fract = scaled.fract();
+        if fract < Ratio::zero() {
+            fract = Ratio::zero() - fract;
+        }
+        let half = Ratio::new_raw(T::one(), T::one() + T::one());
+        if fract < half {
+            Some(trunc)
+        } else if scaled < Ratio::zero() {
+            trunc.checked_sub(&T::one())
+        } else {
+            trunc.checked_add(&T::one())
+        }
+    }
+
+    /// Returns the floor of the logarithm of `self` in the given `base`, that is the largest `k`
+    /// such that `base^k <= self`.
+    ///
+    /// Returns `None` if `self` is not positive, if `base` is not greater than one, or if an
+    /// intermediate value overflows.
+    pub fn ilog(&self, base: &Ratio<T>) -> Option<i32> {
+        let one = Ratio::one();
+        if *self <= Ratio::zero() || *base <= one {
+            return None;
+        }
+        // Working on the reciprocal of values less than one means we only ever divide, so the
+        // intermediate values stay no larger than the original.
+        let below_one = *self < one;
+        let mut x = if below_one {
+            self.recip()
+        } else {
+            self.clone()
+        };
+        // Divide out `base` until `1 <= x < base`
+        let mut k: i32 = 0;
+        while x >= *base {
+            x = x.checked_div(base)?;
+            k = k.checked_add(1)?;
+        }
+        if !below_one {
+            Some(k)
+        } else if x.is_one() {
+            Some(-k)
+        } else {
+            k.checked_add(1).map(|k| -k)
+        }
+    }
+}
+
+// (a/b) / (c/d) = (a*d)/(b*c)
+impl<T> CheckedDiv for Ratio<T>
+where
+    T: Clone + Integer + CheckedMul,
+{
+    #[inline]
+    fn checked_div(&self, rhs: &Ratio<T>) -> Option<Ratio<T>> {
+        if rhs.is_zero() {
+            return None;
+        }
+        let (numer, denom) = if self.denom == rhs.denom {
+            (self.numer.clone(), rhs.numer.clone())
+        } else if self.numer == rhs.numer {
+            (rhs.denom.clone(), self.denom.clone())
+        } else {
+            let gcd_ac = self.numer.gcd(&rhs.numer);
+            let gcd_bd = self.denom.gcd(&rhs.denom);
+            (
+                (self.numer.clone() / gcd_ac.clone())
+                    .checked_mul(&(rhs.denom.clone() / gcd_bd.clone()))?,
+                (self.denom.clone() / gcd_bd).checked_mul(&(rhs.numer.clone() / gcd_ac))?,
+            )
+        };
+        // Manual `reduce()`, avoiding sharp edges
+        if denom.is_zero() {
+            None
+        } else if numer.is_zero() {
+            Some(Self::zero())
+        } else if numer == denom {
+            Some(Self::one())
+        } else {
+            let g = numer.gcd(&denom);
+            let numer = numer / g.clone();
+            let denom = denom / g;
+            let raw = if denom < T::zero() {
+                // We need to keep denom positive, but 2's-complement MIN may
+                // overflow negation -- instead we can check multiplying -1.
+                let n1 = T::zero() - T::one();
+                Ratio::new_raw(numer.checked_mul(&n1)?, denom.checked_mul(&n1)?)
+            } else {
+                Ratio::new_raw(numer, denom)
+            };
+            Some(raw)
+        }
+    }
+}
+
+// As arith_impl! but for Checked{Add,Sub} traits
+macro_rules! checked_arith_impl {
+    (impl $imp:ident, $method:ident) => {
+        // As arith_impl!, cancelling gcd(t, g) before forming the denominator, so these
+        // succeed exactly when the unchecked operators don't overflow.
+        impl<T: Clone + Integer + CheckedMul + $imp> $imp for Ratio<T> {
+            #[inline]
+            fn $method(&self, rhs: &Ratio<T>) -> Option<Ratio<T>> {
+                let g = self.denom.gcd(&rhs.denom);
+                let lhs_factor = self.denom.clone() / g.clone();
+                let lhs_numer = self.numer.checked_mul(&(rhs.denom.clone() / g.clone()))?;
+                let rhs_numer = rhs.numer.checked_mul(&lhs_factor)?;
+                let numer = lhs_numer.$method(&rhs_numer)?;
+                let g2 = numer.gcd(&g);
+                let denom = lhs_factor.checked_mul(&(rhs.denom.clone() / g2.clone()))?;
+                Some(Ratio::new(numer / g2, denom))
+            }
+        }
+    };
+}
+
+// a/b + c/d = (a*(d/g) + c*(b/g))/(b/g*d), where g = gcd(b,d)
+checked_arith_impl!(impl CheckedAdd, checked_add);
+
+// a/b - c/d = (a*(d/g) - c*(b/g))/(b/g*d), where g = gcd(b,d)
+checked_arith_impl!(impl CheckedSub, checked_sub);
+
+// The Euclidean quotient is the integer q with self = q*v + r and 0 <= r < |v|, so it's
+// floor(self/v) for positive v and ceil(self/v) for negative v.
+impl<T: Clone + Integer> Euclid for Ratio<T> {
+    fn div_euclid(&self, v: &Self) -> Self {
+        let quotient = self / v;
+        if *v < Zero::zero() {
+            quotient.ceil()
+        } else {
+            quotient.floor()
+        }
+    }
+
+    fn rem_euclid(&self, v: &Self) -> Self {
+        self - self.div_euclid(v) * v
+    }
+}
+
+impl<T: Clone + Integer + CheckedMul> CheckedEuclid for Ratio<T> {
+    fn checked_div_euclid(&self, v: &Self) -> Option<Self> {
+        self.checked_div_rem_euclid(v).map(|(q, _)| q)
+    }
+
+    fn checked_rem_euclid(&self, v: &Self) -> Option<Self> {
+        self.checked_div_rem_euclid(v).map(|(_, r)| r)
+    }
+
+    // Rather than computing `self - q*v`, where `q*v` may overflow even if the remainder fits,
+    // the remainder is the fractional part of `self/v` scaled by `v`.
+    fn checked_div_rem_euclid(&self, v: &Self) -> Option<(Self, Self)> {
+        let quotient = self.checked_div(v)?;
+        // `quotient` is reduced, so its denominator is positive
+        let (int, rem) = quotient.numer.div_mod_floor(&quotient.denom);
+        let (int, rem) = if *v < Zero::zero() && !rem.is_zero() {
+            (int + T::one(), rem - quotient.denom.clone())
+        } else {
+            (int, rem)
+        };
+        let rem = Ratio::new_raw(rem, quotient.denom).checked_mul(v)?;
+        Some((Ratio::from_integer(int), rem))
+    }
+}
+
+/// Negation only flips the sign of the numerator, so it preserves the reduction state: a reduced
+/// value stays reduced, and a non-reduced value from `new_raw` stays non-reduced. Use
+/// [`Ratio::neg_reduced`] to get a reduced result regardless.
+impl<T> Neg for Ratio<T>
+where
+    T: Clone + Integer + Neg<Output = T>,
+{
+    type Output = Ratio<T>;
+
+    /// **Panics with "negation overflow"** if the numerator is a two's-complement `MIN`, when
+    /// integer overflow isn't already checked. Use `checked_neg` to avoid the panic.
+    #[inline]
+    fn neg(self) -> Ratio<T> {
+        let negative = self.numer < T::zero();
+        let numer = -self.numer;
+        // Only `MIN` keeps its sign when negated with wrapping arithmetic.
+        if negative && numer < T::zero() {
+            panic!("negation overflow");
+        }
+        Ratio::new_raw(numer, self.denom)
+    }
+}
+
+impl<T> Neg for &Ratio<T>
+where
+    T: Clone + Integer + Neg<Output = T>,
+{
+    type Output = Ratio<T>;
+
+    #[inline]
+    fn neg(self) -> Ratio<T> {
+        -self.clone()
+    }
+}
+
+impl<T: Clone + Integer + Neg<Output = T>> Ratio<T> {
+    /// Returns the negation of `self` in lowest terms, with a positive denominator.
+    ///
+    /// **Panics if `denom` is zero.**
+    pub fn neg_reduced(&self) -> Ratio<T> {
+        -self.reduced()
+    }
+}
+
+/// Like `Neg`, this only flips the sign of the numerator, returning `None` if that overflows.
+impl<T> CheckedNeg for Ratio<T>
+where
+    T: Clone + Integer + CheckedNeg,
+{
+    #[inline]
+    fn checked_neg(&self) -> Option<Ratio<T>> {
+        let numer = self.numer.checked_neg()?;
+        Some(Ratio::new_raw(numer, self.denom.clone()))
+    }
+}
+
+impl<T> Inv for Ratio<T>
+where
+    T: Clone + Integer,
+{
+    type Output = Ratio<T>;
+
+    #[inline]
+    fn inv(self) -> Ratio<T> {
+        self.recip()
+    }
+}
+
+impl<T> Inv for &Ratio<T>
+where
+    T: Clone + Integer,
+{
+    type Output = Ratio<T>;
+
+    #[inline]
+    fn inv(self) -> Ratio<T> {
+        self.recip()
+    }
+}
+
+// Constants
+impl<T: ConstZero + ConstOne> Ratio<T> {
+    /// A constant `Ratio` 0/1.
+    pub const ZERO: Self = Self::new_raw(T::ZERO, T::ONE);
+}
+
+impl<T: Clone + Integer + ConstZero + ConstOne> ConstZero for Ratio<T> {
+    const ZERO: Self = Self::ZERO;
+}
+
+impl<T: Clone + Integer> Zero for Ratio<T> {
+    #[inline]
+    fn zero() -> Ratio<T> {
+        Ratio::new_raw(Zero::zero(), One::one())
+    }
+
+    #[inline]
+    fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+
+    #[inline]
+    fn set_zero(&mut self) {
+        self.numer.set_zero();
+        self.denom.set_one();
+    }
+}
+
+impl<T: ConstOne> Ratio<T> {
+    /// A constant `Ratio` 1/1.
+    pub const ONE: Self = Self::new_raw(T::ONE, T::ONE);
+}
+
+impl<T: Clone + Integer + ConstOne> ConstOne for Ratio<T> {
+    const ONE: Self = Self::ONE;
+}
+
+impl<T: Clone + Integer> One for Ratio<T> {
+    #[inline]
+    fn one() -> Ratio<T> {
+        Ratio::new_raw(One::one(), One::one())
+    }
+
+    #[inline]
+    fn is_one(&self) -> bool {
+        self.numer == self.denom
+    }
+
+    #[inline]
+    fn set_one(&mut self) {
+        self.numer.set_one();
+        self.denom.set_one();
+    }
+}
+
+impl<T: Clone + Integer> Num for Ratio<T> {
+    type FromStrRadixErr = ParseRatioError;
+
+    /// Parses `numer/denom` where the numbers are in base `radix`.
+    ///
+    /// Like `FromStr`, either part may have a leading sign, and the result is normalized to a
+    /// positive denominator, so `"3/-4"` and `"-3/4"` parse to the same value.
+    fn from_str_radix(s: &str, radix: u32) -> Result<Ratio<T>, ParseRatioError> {
+        if s.splitn(2, '/').count() == 2 {
+            let mut parts = s
+                .splitn(2, '/')
+                .map(|ss| T::from_str_radix(ss, radix).map_err(|_| int_parse_error(ss, radix)));
+            let numer: T = parts.next().unwrap()?;
+            let denom: T = parts.next().unwrap()?;
+            if denom.is_zero() {
+                Err(ParseRatioError {
+                    kind: RatioErrorKind::ZeroDenominator,
+                })
+            } else {
+                let ratio = Ratio::new_raw(numer, denom);
+                reduce_parsed(ratio, s, |digits| T::from_str_radix(digits, radix).is_ok())
+            }
+        } else {
+            Err(ParseRatioError {
+                kind: RatioErrorKind::ParseError,
+            })
+        }
+    }
+}
+
+impl<T: Clone + Integer + Signed> Signed for Ratio<T> {
+    /// Returns the absolute value, with both the numerator and denominator made non-negative,
+    /// so unreduced inputs like `new_raw(1, -2)` also get a positive denominator.
+    #[inline]
+    fn abs(&self) -> Ratio<T> {
+        Ratio::new_raw(self.numer.abs(), self.denom.abs())
+    }
+
+    #[inline]
+    fn abs_sub(&self, other: &Ratio<T>) -> Ratio<T> {
+        if *self <= *other {
+            Zero::zero()
+        } else {
+            self - other
+        }
+    }
+
+    #[inline]
+    fn signum(&self) -> Ratio<T> {
+        if self.is_positive() {
+            Self::one()
+        } else if self.is_zero() {
+            Self::zero()
+        } else {
+            -Self::one()
+        }
+    }
+
+    #[inline]
+    fn is_positive(&self) -> bool {
+        (self.numer.is_positive() && self.denom.is_positive())
+            || (self.numer.is_negative() && self.denom.is_negative())
+    }
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        (self.numer.is_negative() && self.denom.is_positive())
+            || (self.numer.is_positive() && self.denom.is_negative())
+    }
+}
+
+impl<T: Clone + Integer + Signed> Ratio<T> {
+    /// Splits into a sign of `-1`, `0` or `1`, and the absolute value.
+    pub fn split_sign(&self) -> (i8, Ratio<T>) {
+        if self.is_negative() {
+            (-1, -self.clone())
+        } else if self.is_zero() {
+            (0, self.clone())
+        } else {
+            (1, self.clone())
+        }
+    }
+
+    /// Returns the absolute value, negated if `negative` is true.
+    ///
+    /// This is the inverse of `split_sign`, with zero staying zero either way.
+    pub fn with_sign(&self, negative: bool) -> Ratio<T> {
+        let abs = self.abs();
+        if negative {
+            -abs
+        } else {
+            abs
+        }
+    }
+
+    /// Returns the inverse of the reduced denominator modulo `modulus`, found with the extended
+    /// Euclidean algorithm, in the range `0..modulus`.
+    ///
+    /// Multiplying the reduced numerator by this maps the ratio into the integers modulo
+    /// `modulus`. Returns `None` if `modulus` is not positive or if it isn't coprime to the
+    /// denominator.
+    ///
+    /// **Panics if `denom` is zero.**
+    pub fn denom_inverse_mod(&self, modulus: &T) -> Option<T> {
+        if !modulus.is_positive() {
+            return None;
+        }
+        let denom = self.reduced().denom;
+        let egcd = denom.mod_floor(modulus).extended_gcd(modulus);
+        if egcd.gcd.is_one() {
+            Some(egcd.x.mod_floor(modulus))
+        } else {
+            None
+        }
+    }
+
+    /// Deconstructs into `(is_negative, |numer|, |denom|)`, keeping the sign separately from a
+    /// non-negative numerator and a positive denominator.
+    ///
+    /// The parts are not reduced, and taking the absolute value of a two's-complement `MIN`
+    /// part may overflow.
+    pub fn into_unsigned_parts(self) -> (bool, T, T) {
+        (self.is_negative(), self.numer.abs(), self.denom.abs())
+    }
+}
+
+impl<T: Clone + Integer + Signed + CheckedMul> Ratio<T> {
+    /// Returns the absolute value, or `None` if negating the numerator would overflow.
+    ///
+    /// This is the case for a negative value whose numerator is the minimum of a
+    /// two's-complement type, like `Ratio::new(i8::MIN, 1)`.
+    pub fn checked_abs(&self) -> Option<Ratio<T>> {
+        if self.is_negative() {
+            // 2's-complement MIN may overflow negation -- instead we can check multiplying -1.
+            let n1 = T::zero() - T::one();
+            Some(Ratio::new_raw(
+                self.numer.checked_mul(&n1)?,
+                self.denom.clone(),
+            ))
+        } else {
+            Some(self.clone())
+        }
+    }
+}
+
+// String conversions
+macro_rules! impl_formatting {
+    ($fmt_trait:ident, $prefix:expr, $fmt_str:expr, $fmt_alt:expr) => {
+        impl<T: $fmt_trait + Clone + Integer> $fmt_trait for Ratio<T> {
+            #[cfg(feature = "std")]
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                // Any representation of zero is displayed canonically
+                let pre_pad = if self.is_integer() || self.numer.is_zero() {
+                    format!($fmt_str, self.numer)
+                } else {
+                    if f.alternate() {
+                        format!(concat!($fmt_str, "/", $fmt_alt), self.numer, self.denom)
+                    } else {
+                        format!(concat!($fmt_str, "/", $fmt_str), self.numer, self.denom)
+                    }
+                };
+                if let Some(pre_pad) = pre_pad.strip_prefix("-") {
+                    f.pad_integral(false, $prefix, pre_pad)
+                } else {
+                    f.pad_integral(true, $prefix, &pre_pad)
+                }
+            }
+            #[cfg(not(feature = "std"))]
+            fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                let plus = if f.sign_plus() && self.numer >= T::zero() {
+                    "+"
+                } else {
+                    ""
+                };
+                if self.is_integer() || self.numer.is_zero() {
+                    if f.alternate() {
+                        write!(f, concat!("{}", $fmt_alt), plus, self.numer)
+                    } else {
+                        write!(f, concat!("{}", $fmt_str), plus, self.numer)
+                    }
+                } else {
+                    if f.alternate() {
+                        write!(
+                            f,
+                            concat!("{}", $fmt_alt, "/", $fmt_alt),
+                            plus, self.numer, self.denom
+                        )
+                    } else {
+                        write!(
+                            f,
+                            concat!("{}", $fmt_str, "/", $fmt_str),
+                            plus, self.numer, self.denom
+                        )
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_formatting!(Display, "", "{}", "{:#}");
+impl_formatting!(Octal, "0o", "{:o}", "{:#o}");
+impl_formatting!(Binary, "0b", "{:b}", "{:#b}");
+impl_formatting!(LowerHex, "0x", "{:x}", "{:#x}");
+impl_formatting!(UpperHex, "0x", "{:X}", "{:#X}");
+impl_formatting!(LowerExp, "", "{:e}", "{:#e}");
+impl_formatting!(UpperExp, "", "{:E}", "{:#E}");
+
+impl<T: Display + Clone + Integer> Ratio<T> {
+    /// Writes the plain `Display` form of the ratio, `numer/denom` or just `numer`, directly
+    /// into `w`.
+    ///
+    /// Unlike `format!` or `to_string`, this needs no intermediate allocation, so a buffer can
+    /// be reused across many values. Formatting flags like width and sign are not applied.
+    pub fn write_to<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        if self.is_integer() || self.numer.is_zero() {
+            write!(w, "{}", self.numer)
+        } else {
+            write!(w, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Clone + Integer + ToPrimitive + FromPrimitive> Ratio<T> {
+    /// Renders the ratio as `numer/denom`, or just `numer` for integers, with both parts in
+    /// base `radix`.
+    ///
+    /// Digits above 9 are lowercase letters. Non-integers can be parsed back with
+    /// `from_str_radix`, which always expects the `/denom` part.
+    ///
+    /// **Panics if `radix` is not in the range `2..=36`.**
+    pub fn to_string_radix(&self, radix: u32) -> std::string::String {
+        assert!(
+            (2..=36).contains(&radix),
+            "radix must be in the range 2..=36"
+        );
+        let mut s = std::string::String::new();
+        push_int_radix(&mut s, &self.numer, radix);
+        if !self.is_integer() && !self.numer.is_zero() {
+            s.push('/');
+            push_int_radix(&mut s, &self.denom, radix);
+        }
+        s
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Display + Clone + Integer> Ratio<T> {
+    /// Renders the ratio like `Display`, but with the digits of the numerator and denominator
+    /// grouped in threes with `sep`, e.g. `1,234,567/1,000`.
+    pub fn to_grouped_string(&self, sep: char) -> std::string::String {
+        let mut s = std::string::String::new();
+        push_grouped(&mut s, &format!("{}", self.numer), sep);
+        if !self.is_integer() && !self.numer.is_zero() {
+            s.push('/');
+            push_grouped(&mut s, &format!("{}", self.denom), sep);
+        }
+        s
+    }
+
+    /// Renders the ratio in engineering notation with `sig_figs` significant digits, e.g.
+    /// `1.00e-6` for `1/1000000`, or `12.3e3` for `12345`.
+    ///
+    /// The exponent is a multiple of three, with one to three digits before the decimal point.
+    /// The digits come from exact long division, rounding half-way cases away from zero.
+    ///
+    /// **Panics if `sig_figs` is zero.**
+    pub fn to_engineering_string(&self, sig_figs: usize) -> std::string::String {
+        assert!(sig_figs > 0, "sig_figs == 0");
+
+        let mut digits = self.decimal_digits();
+        let integer = format!("{}", digits.next().unwrap_or_else(T::zero));
+        let integer = integer.trim_start_matches('-');
+
+        // The significant digits, plus one more for rounding, and the decimal exponent of the
+        // first of them.
+        let mut sig = std::vec::Vec::with_capacity(sig_figs + 1);
+        let mut exp10 = 0isize;
+        if integer != "0" {
+            exp10 = integer.len() as isize - 1;
+            sig.extend(integer.bytes().map(|b| b - b'0').take(sig_figs + 1));
+        }
+        for digit in digits {
+            if sig.len() > sig_figs {
+                break;
+            }
+            let digit = format!("{}", digit).as_bytes()[0] - b'0';
+            if sig.is_empty() {
+                exp10 -= 1;
+                if digit == 0 {
+                    continue;
+                }
+            }
+            sig.push(digit);
+        }
+        if sig.is_empty() {
+            exp10 = 0;
+        }
+        sig.resize(sig_figs + 1, 0);
+
+        if sig.pop() >= Some(5) {
+            match sig.iter().rposition(|&d| d < 9) {
+                Some(i) => {
+                    sig[i] += 1;
+                    sig[i + 1..].iter_mut().for_each(|d| *d = 0);
+                }
+                None => {
+                    // 9.99 rounds up to 10.0
+                    sig.iter_mut().for_each(|d| *d = 0);
+                    sig[0] = 1;
+                    exp10 += 1;
+                }
+            }
+        }
+
+        let exp3 = exp10.div_euclid(3) * 3;
+        let int_len = (exp10 - exp3 + 1) as usize;
+        if sig.len() < int_len {
+            sig.resize(int_len, 0);
+        }
+
+        let mut s = std::string::String::new();
+        if self.cmp_zero() == cmp::Ordering::Less {
+            s.push('-');
+        }
+        for (i, &d) in sig.iter().enumerate() {
+            if i == int_len {
+                s.push('.');
+            }
+            s.push(char::from(b'0' + d));
+        }
+        s.push_str(&format!("e{}", exp3));
+        s
+    }
+}
+
+/// Appends the decimal integer `int`, with its digits grouped in threes.
+#[cfg(feature = "std")]
+fn push_grouped(s: &mut std::string::String, int: &str, sep: char) {
+    let digits = match int.strip_prefix('-') {
+        Some(digits) => {
+            s.push('-');
+            digits
+        }
+        None => int,
+    };
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            s.push(sep);
+        }
+        s.push(c);
+    }
+}
+
+#[cfg(feature = "std")]
+fn push_int_radix<T>(s: &mut std::string::String, x: &T, radix: u32)
+where
+    T: Clone + Integer + ToPrimitive + FromPrimitive,
+{
+    let base = T::from_u32(radix).expect("radix fits in T");
+    let mut digits = std::vec::Vec::new();
+    let mut x = x.clone();
+    let negative = x < T::zero();
+    loop {
+        // Truncating division, so a negative remainder is small enough to negate
+        let (q, r) = x.div_rem(&base);
+        let r = if negative { T::zero() - r } else { r };
+        let d = r.to_u32().expect("digit fits in u32");
+        digits.push(std::char::from_digit(d, radix).unwrap());
+        if q.is_zero() {
+            break;
+        }
+        x = q;
+    }
+    if negative {
+        s.push('-');
+    }
+    s.extend(digits.iter().rev());
+}
+
+impl<T: FromStr + Clone + Integer> FromStr for Ratio<T> {
+    type Err = ParseRatioError;
+
+    /// Parses `numer/denom` or just `numer`.
+    ///
+    /// Either part may have a leading sign, and the result is normalized to a positive
+    /// denominator, so `"3/-4"` and `"-3/4"` parse to the same value.
+    fn from_str(s: &str) -> Result<Ratio<T>, ParseRatioError> {
+        let ratio = Ratio::from_str_raw(s)?;
+        reduce_parsed(ratio, s, |digits| T::from_str(digits).is_ok())
+    }
+}
+
+impl<T: FromStr + Clone + Integer> Ratio<T> {
+    /// Parses `numer/denom` or just `numer` like `FromStr`, but keeps the parts exactly as
+    /// written, without reducing or normalizing the sign.
+    ///
+    /// A zero denominator is still rejected.
+    pub fn from_str_raw(s: &str) -> Result<Ratio<T>, ParseRatioError> {
+        let mut split = s.splitn(2, '/');
+
+        let n = split.next().ok_or(ParseRatioError {
+            kind: RatioErrorKind::ParseError,
+        })?;
+        let num = FromStr::from_str(n).map_err(|_| int_parse_error(n, 10))?;
+
+        let d = split.next().unwrap_or("1");
+        let den = FromStr::from_str(d).map_err(|_| int_parse_error(d, 10))?;
+
+        if Zero::is_zero(&den) {
+            Err(ParseRatioError {
+                kind: RatioErrorKind::ZeroDenominator,
+            })
+        } else {
+            Ok(Ratio::new_raw(num, den))
+        }
+    }
+
+    /// Parses like `FromStr`, but also accepts a trailing slash with no denominator, reading
+    /// `5/` as `5/1`, for forgiving handling of typed input.
+    pub fn from_str_lenient(s: &str) -> Result<Ratio<T>, ParseRatioError> {
+        Ratio::from_str(s.strip_suffix('/').unwrap_or(s))
+    }
+}
+
+impl<T> From<Ratio<T>> for (T, T) {
+    fn from(val: Ratio<T>) -> Self {
+        (val.numer, val.denom)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Ratio<T>
+where
+    T: serde::Serialize + Clone + Integer + PartialOrd,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        (self.numer(), self.denom()).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Ratio<T>
+where
+    T: serde::Deserialize<'de> + Clone + Integer + PartialOrd,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+        let (numer, denom): (T, T) = serde::Deserialize::deserialize(deserializer)?;
+        if denom.is_zero() {
+            Err(Error::custom(ZeroDenominator))
+        } else {
+            Ok(Ratio::new_raw(numer, denom))
+        }
+    }
+}
+
+/// Compares the reduced forms of both ratios without branching on their values, so equal
+/// ratios compare equal regardless of representation.
+///
+/// Only the final comparison runs in constant time. Reducing each side uses a gcd, whose
+/// running time depends on the values, unless they are already in lowest terms.
+///
+/// **Panics if `denom` is zero.**
+#[cfg(feature = "subtle")]
+impl<T> subtle::ConstantTimeEq for Ratio<T>
+where
+    T: Clone + Integer + subtle::ConstantTimeEq,
+{
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        let a = self.reduced();
+        let b = other.reduced();
+        a.numer.ct_eq(&b.numer) & a.denom.ct_eq(&b.denom)
+    }
+}
+
+/// The error returned when constructing a `Ratio` with a zero denominator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ZeroDenominator;
+
+impl fmt::Display for ZeroDenominator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        RatioErrorKind::ZeroDenominator.description().fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ZeroDenominator {}
+
+// FIXME: Bubble up specific errors
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ParseRatioError {
+    kind: RatioErrorKind,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum RatioErrorKind {
+    ParseError,
+    ZeroDenominator,
+    Overflow,
+}
+
+/// Reduces a `ratio` parsed from `s`, or returns an overflow error if making its denominator
+/// positive can't be represented.
+///
+/// Normalizing the sign negates both parts after dividing out their gcd, which only overflows
+/// for a two's-complement `MIN` that no common factor reduces. That is also the only negative
+/// value whose digits alone fail to `parse`, which detects it without a `Bounded` constraint.
+fn reduce_parsed<T: Clone + Integer>(
+    mut ratio: Ratio<T>,
+    s: &str,
+    parse: impl Fn(&str) -> bool,
+) -> Result<Ratio<T>, ParseRatioError> {
+    if ratio.denom < T::zero() && !ratio.numer.is_zero() && ratio.numer != ratio.denom {
+        let has_min = s.split('/').any(|part| {
+            part.strip_prefix('-')
+                .map_or(false, |digits| !parse(digits))
+        });
+        if has_min && ratio.numer.gcd(&ratio.denom).is_one() {
+            return Err(ParseRatioError {
+                kind: RatioErrorKind::Overflow,
+            });
+        }
+    }
+    ratio.reduce();
+    Ok(ratio)
+}
+
+/// Classifies a failure to parse the integer `s`: a well-formed integer in `radix` can only have
+/// failed by being out of range for the type.
+fn int_parse_error(s: &str, radix: u32) -> ParseRatioError {
+    let digits = s.strip_prefix(|c| c == '+' || c == '-').unwrap_or(s);
+    let kind = if !digits.is_empty() && digits.chars().all(|c| c.is_digit(radix)) {
+        RatioErrorKind::Overflow
+    } else {
+        RatioErrorKind::ParseError
+    };
+    ParseRatioError { kind }
+}
+
+impl fmt::Display for ParseRatioError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.kind.description().fmt(f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ParseRatioError {
     #[allow(deprecated)]
     fn description(&self) -> &str {
         self.kind.description()
     }
 }
 
-impl RatioErrorKind {
-    fn description(&self) -> &'static str {
-        match *self {
-            RatioErrorKind::ParseError => "failed to parse integer",
-            RatioErrorKind::ZeroDenominator => "zero value denominator",
+impl RatioErrorKind {
+    fn description(&self) -> &'static str {
+        match *self {
+            RatioErrorKind::ParseError => "failed to parse integer",
+            RatioErrorKind::ZeroDenominator => "zero value denominator",
+            RatioErrorKind::Overflow => "integer out of range",
+        }
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl FromPrimitive for Ratio<BigInt> {
+    fn from_i64(n: i64) -> Option<Self> {
+        Some(Ratio::from_integer(n.into()))
+    }
+
+    fn from_i128(n: i128) -> Option<Self> {
+        Some(Ratio::from_integer(n.into()))
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        Some(Ratio::from_integer(n.into()))
+    }
+
+    fn from_u128(n: u128) -> Option<Self> {
+        Some(Ratio::from_integer(n.into()))
+    }
+
+    fn from_f32(n: f32) -> Option<Self> {
+        Ratio::from_float(n)
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        Ratio::from_float(n)
+    }
+}
+
+macro_rules! from_primitive_integer {
+    ($typ:ty, $approx:ident) => {
+        impl FromPrimitive for Ratio<$typ> {
+            fn from_i64(n: i64) -> Option<Self> {
+                <$typ as FromPrimitive>::from_i64(n).map(Ratio::from_integer)
+            }
+
+            fn from_i128(n: i128) -> Option<Self> {
+                <$typ as FromPrimitive>::from_i128(n).map(Ratio::from_integer)
+            }
+
+            fn from_u64(n: u64) -> Option<Self> {
+                <$typ as FromPrimitive>::from_u64(n).map(Ratio::from_integer)
+            }
+
+            fn from_u128(n: u128) -> Option<Self> {
+                <$typ as FromPrimitive>::from_u128(n).map(Ratio::from_integer)
+            }
+
+            fn from_f32(n: f32) -> Option<Self> {
+                $approx(n, 10e-20, 30)
+            }
+
+            fn from_f64(n: f64) -> Option<Self> {
+                $approx(n, 10e-20, 30)
+            }
+        }
+    };
+}
+
+from_primitive_integer!(i8, approximate_float);
+from_primitive_integer!(i16, approximate_float);
+from_primitive_integer!(i32, approximate_float);
+from_primitive_integer!(i64, approximate_float);
+from_primitive_integer!(i128, approximate_float);
+from_primitive_integer!(isize, approximate_float);
+
+from_primitive_integer!(u8, approximate_float_unsigned);
+from_primitive_integer!(u16, approximate_float_unsigned);
+from_primitive_integer!(u32, approximate_float_unsigned);
+from_primitive_integer!(u64, approximate_float_unsigned);
+from_primitive_integer!(u128, approximate_float_unsigned);
+from_primitive_integer!(usize, approximate_float_unsigned);
+
+impl<T: Integer + Signed + Bounded + NumCast + Clone> Ratio<T> {
+    pub fn approximate_float<F: FloatCore + NumCast>(f: F) -> Option<Ratio<T>> {
+        // 1/10e-20 < 1/2**32 which seems like a good default, and 30 seems
+        // to work well. Might want to choose something based on the types in the future, e.g.
+        // T::max().recip() and T::bits() or something similar.
+        let epsilon = <F as NumCast>::from(10e-20).expect("Can't convert 10e-20");
+        Self::approximate_float_with_error(f, epsilon, 30).map(|(r, _)| r)
+    }
+
+    /// Approximates a float with a continued fraction, stopping once the approximation is within
+    /// `max_error` or after `max_iterations` terms. Returns the approximation along with the
+    /// achieved error `|approximation - f|`.
+    pub fn approximate_float_with_error<F: FloatCore + NumCast>(
+        f: F,
+        max_error: F,
+        max_iterations: usize,
+    ) -> Option<(Ratio<T>, F)> {
+        let r = approximate_float(f, max_error, max_iterations)?;
+        let error = approximation_error(&r, f)?;
+        Some((r, error))
+    }
+}
+
+impl<T: Integer + Unsigned + Bounded + NumCast + Clone> Ratio<T> {
+    pub fn approximate_float_unsigned<F: FloatCore + NumCast>(f: F) -> Option<Ratio<T>> {
+        // 1/10e-20 < 1/2**32 which seems like a good default, and 30 seems
+        // to work well. Might want to choose something based on the types in the future, e.g.
+        // T::max().recip() and T::bits() or something similar.
+        let epsilon = <F as NumCast>::from(10e-20).expect("Can't convert 10e-20");
+        Self::approximate_float_unsigned_with_error(f, epsilon, 30).map(|(r, _)| r)
+    }
+
+    /// As [`Ratio::approximate_float_with_error`], for unsigned element types.
+    pub fn approximate_float_unsigned_with_error<F: FloatCore + NumCast>(
+        f: F,
+        max_error: F,
+        max_iterations: usize,
+    ) -> Option<(Ratio<T>, F)> {
+        let r = approximate_float_unsigned(f, max_error, max_iterations)?;
+        let error = approximation_error(&r, f)?;
+        Some((r, error))
+    }
+}
+
+impl<T: Clone + Integer + CheckedAdd + CheckedMul + FromPrimitive> Ratio<T> {
+    /// Returns the closest ratio to π with a denominator no larger than `max_denom`, like `22/7`
+    /// for a bound from 7 to 56.
+    ///
+    /// This uses the known continued fraction of π, whose first 81 terms cover denominators
+    /// past `u128::MAX`. Larger bounds give the last convergent from those terms. For fixed-size
+    /// types, the numerator must also fit, so a bound above a quarter of the type's maximum may
+    /// instead give the last convergent whose numerator fits, like `22/7` for `255u8`.
+    ///
+    /// **Panics if `max_denom` is less than one.**
+    pub fn approximate_pi(max_denom: T) -> Ratio<T> {
+        const TERMS: [u16; 81] = [
+            3, 7, 15, 1, 292, 1, 1, 1, 2, 1, 3, 1, 14, 2, 1, 1, 2, 2, 2, 2, 1, 84, 2, 1, 1, 15, 3,
+            13, 1, 4, 2, 6, 6, 99, 1, 2, 2, 6, 3, 5, 1, 1, 6, 8, 1, 7, 1, 2, 3, 7, 1, 2, 1, 1, 12,
+            1, 1, 1, 3, 1, 1, 8, 1, 1, 2, 1, 6, 1, 1, 5, 2, 2, 3, 1, 2, 4, 4, 16, 1, 161, 45,
+        ];
+        let terms = TERMS
+            .iter()
+            .map(|&a| T::from_u16(a).expect("term fits in T"));
+        best_approximation(terms, &max_denom)
+    }
+
+    /// Returns the closest ratio to e with a denominator no larger than `max_denom`, like `19/7`
+    /// for a bound from 7 to 17.
+    ///
+    /// This uses the continued fraction `[2; 1, 2, 1, 1, 4, 1, 1, 6, ...]`, so there is no limit
+    /// on the bound. For fixed-size types, the numerator must also fit, so a bound above a third
+    /// of the type's maximum may instead give the last convergent whose numerator fits.
+    ///
+    /// **Panics if `max_denom` is less than one.**
+    pub fn approximate_e(max_denom: T) -> Ratio<T> {
+        let pattern = (1u64..).flat_map(|k| [1, 2 * k, 1]);
+        let terms = core::iter::once(2)
+            .chain(pattern)
+            .map(|a| T::from_u64(a).expect("term fits in T"));
+        best_approximation(terms, &max_denom)
+    }
+}
+
+impl<T: Integer + NumCast + Clone + CheckedAdd + CheckedMul> Ratio<T> {
+    /// Returns the closest rational to `x` with a denominator no larger than `max_denom`, but
+    /// only if that is within `tol` of `x`.
+    ///
+    /// This accepts floats that are close to a "nice" fraction, like `0.333333` for `1/3`, and
+    /// rejects others. Returns `None` for non-finite `x`, if `max_denom` is less than one, or if
+    /// the approximation isn't representable, like a negative value for an unsigned type.
+    pub fn from_f64_within(x: f64, max_denom: T, tol: f64) -> Option<Ratio<T>> {
+        if !x.is_finite() || max_denom < T::one() {
+            return None;
+        }
+
+        // Continued fraction convergents p1/q1 of |x|, stopping before the denominator exceeds
+        // the bound, then choosing between the last convergent and the best semiconvergent.
+        let mut y = x.abs();
+        let (mut p0, mut q0, mut p1, mut q1) = (T::zero(), T::one(), T::one(), T::zero());
+        loop {
+            let a_f = y.floor();
+            let a = match <T as NumCast>::from(a_f) {
+                Some(a) => a,
+                None if q1.is_zero() => return None,
+                None => break,
+            };
+            let q2 = match a.checked_mul(&q1).and_then(|aq| aq.checked_add(&q0)) {
+                Some(q2) if q2 <= max_denom => q2,
+                _ => {
+                    // The semiconvergent's denominator is in range, but its numerator may not
+                    // fit, in which case the convergent is the best representable choice.
+                    let k = (max_denom.clone() - q0.clone()) / q1.clone();
+                    let p = k.checked_mul(&p1).and_then(|kp| kp.checked_add(&p0));
+                    let q = k.checked_mul(&q1).and_then(|kq| kq.checked_add(&q0));
+                    if let (Some(p), Some(q)) = (p, q) {
+                        let err = |p: &T, q: &T| -> Option<f64> {
+                            Some((p.to_f64()? / q.to_f64()? - x.abs()).abs())
+                        };
+                        if err(&p, &q)? < err(&p1, &q1)? {
+                            p1 = p;
+                            q1 = q;
+                        }
+                    }
+                    break;
+                }
+            };
+            let p2 = match a.checked_mul(&p1).and_then(|ap| ap.checked_add(&p0)) {
+                Some(p2) => p2,
+                None if q1.is_zero() => return None,
+                None => break,
+            };
+            p0 = p1;
+            q0 = q1;
+            p1 = p2;
+            q1 = q2;
+
+            let fract = y - a_f;
+            if fract == 0.0 {
+                break;
+            }
+            y = fract.recip();
+        }
+
+        let mut r = Ratio::new(p1, q1);
+        if x < 0.0 && !r.is_zero() {
+            let n1 = <T as NumCast>::from(-1)?;
+            r.numer = r.numer * n1;
+        }
+        if approximation_error(&r, x)? <= tol {
+            Some(r)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(any(feature = "std", feature = "libm"))]
+impl<T: Integer + NumCast + Clone + CheckedAdd + CheckedMul> Ratio<T> {
+    /// Returns the size of the frequency ratio `self` in cents, `1200 * log2(self)`.
+    ///
+    /// The result is `NaN` for negative ratios and `-∞` for zero.
+    pub fn to_cents(&self) -> f64
+    where
+        Ratio<T>: ToPrimitive,
+    {
+        1200.0 * num_traits::Float::log2(self.to_f64().unwrap_or(f64::NAN))
+    }
+
+    /// Returns the frequency ratio closest to `cents`, with a denominator no larger than
+    /// `max_denom`.
+    ///
+    /// Returns `None` if `cents` is not finite, if `max_denom` is less than one, or if the
+    /// ratio isn't representable.
+    pub fn from_cents(cents: f64, max_denom: T) -> Option<Ratio<T>> {
+        let x = num_traits::Float::exp2(cents / 1200.0);
+        Ratio::from_f64_within(x, max_denom, f64::INFINITY)
+    }
+}
+
+fn approximation_error<T, F>(r: &Ratio<T>, val: F) -> Option<F>
+where
+    T: Integer + NumCast + Clone,
+    F: FloatCore + NumCast,
+{
+    let n_f = <F as NumCast>::from(r.numer.clone())?;
+    let d_f = <F as NumCast>::from(r.denom.clone())?;
+    Some((n_f / d_f - val).abs())
+}
+
+/// Returns the closest ratio with a denominator no larger than `max_denom` to the irrational
+/// number with the continued fraction `terms`.
+///
+/// This picks between the last convergent in range and the best semiconvergent from the terms
+/// alone, so the number itself is never needed. If a numerator would overflow first, this
+/// stops at the last convergent whose numerator fits.
+fn best_approximation<T, I>(terms: I, max_denom: &T) -> Ratio<T>
+where
+    T: Clone + Integer + CheckedAdd + CheckedMul,
+    I: Iterator<Item = T>,
+{
+    assert!(*max_denom >= T::one(), "max_denom < 1");
+
+    let mut conv = Convergents::new(terms);
+    let (a, k) = match conv.clamp(max_denom, |conv, a| conv.checked_numer(a).is_some()) {
+        Some(clamped) => clamped,
+        None => return conv.convergent(),
+    };
+
+    // The semiconvergent with `k` is closer than the convergent if `2k > a`. On a tie, that
+    // depends on whether the rest of the continued fraction exceeds `q1/q0`.
+    let two_k = k.clone() + k.clone();
+    let semi_closer = match two_k.cmp(&a) {
+        cmp::Ordering::Less => false,
+        cmp::Ordering::Greater => true,
+        cmp::Ordering::Equal => {
+            let ratio = Ratio::new_raw(conv.q1.clone(), conv.q0.clone());
+            !conv.q0.is_zero() && tail_exceeds(conv.terms.by_ref(), ratio)
+        }
+    };
+    if semi_closer && conv.checked_numer(&k).is_some() {
+        conv.semiconvergent(&k)
+    } else {
+        conv.convergent()
+    }
+}
+
+/// Returns true if the continued fraction `terms` is larger than the positive ratio `r`,
+/// comparing the terms against those of `r` one at a time. Running out of terms returns
+/// false.
+fn tail_exceeds<T, I>(terms: I, mut r: Ratio<T>) -> bool
+where
+    T: Clone + Integer,
+    I: Iterator<Item = T>,
+{
+    // Each level compares reciprocals, which flips the result.
+    let mut exceeds = true;
+    for a in terms {
+        let (floor, rem) = r.numer.div_mod_floor(&r.denom);
+        if a != floor {
+            return (a > floor) == exceeds;
+        }
+        if rem.is_zero() {
+            // The terms continue past `a`, so they are larger.
+            return exceeds;
+        }
+        r = Ratio::new_raw(r.denom, rem);
+        exceeds = !exceeds;
+    }
+    false
+}
+
+fn approximate_float<T, F>(val: F, max_error: F, max_iterations: usize) -> Option<Ratio<T>>
+where
+    T: Integer + Signed + Bounded + NumCast + Clone,
+    F: FloatCore + NumCast,
+{
+    let negative = val.is_sign_negative();
+    let abs_val = val.abs();
+
+    let r = approximate_float_unsigned(abs_val, max_error, max_iterations)?;
+
+    // Make negative again if needed
+    Some(if negative { r.neg() } else { r })
+}
+
+// No Unsigned constraint because this also works on positive integers and is called
+// like that, see above
+fn approximate_float_unsigned<T, F>(val: F, max_error: F, max_iterations: usize) -> Option<Ratio<T>>
+where
+    T: Integer + Bounded + NumCast + Clone,
+    F: FloatCore + NumCast,
+{
+    // Continued fractions algorithm
+    // https://web.archive.org/web/20200629111319/http://mathforum.org:80/dr.math/faq/faq.fractions.html#decfrac
+
+    if val < F::zero() || val.is_nan() {
+        return None;
+    }
+
+    let mut q = val;
+    let mut n0 = T::zero();
+    let mut d0 = T::one();
+    let mut n1 = T::one();
+    let mut d1 = T::zero();
+
+    let t_max = T::max_value();
+    let t_max_f = <F as NumCast>::from(t_max.clone())?;
+
+    // 1/epsilon > T::MAX
+    let epsilon = t_max_f.recip();
+
+    // Overflow. `T::max_value()` may round up as a float, like `i128::MAX` to 2^127, so a value
+    // equal to `t_max_f` still has to be checked exactly.
+    if q > t_max_f || (q == t_max_f && <T as NumCast>::from(q).is_none()) {
+        return None;
+    }
+
+    for _ in 0..max_iterations {
+        let a = match <T as NumCast>::from(q) {
+            None => break,
+            Some(a) => a,
+        };
+
+        let a_f = match <F as NumCast>::from(a.clone()) {
+            None => break,
+            Some(a_f) => a_f,
+        };
+        let f = q - a_f;
+
+        // Prevent overflow
+        if !a.is_zero()
+            && (n1 > t_max.clone() / a.clone()
+                || d1 > t_max.clone() / a.clone()
+                || a.clone() * n1.clone() > t_max.clone() - n0.clone()
+                || a.clone() * d1.clone() > t_max.clone() - d0.clone())
+        {
+            break;
+        }
+
+        let n = a.clone() * n1.clone() + n0.clone();
+        let d = a.clone() * d1.clone() + d0.clone();
+
+        n0 = n1;
+        d0 = d1;
+        n1 = n.clone();
+        d1 = d.clone();
+
+        // Simplify fraction. Doing so here instead of at the end
+        // allows us to get closer to the target value without overflows
+        let g = Integer::gcd(&n1, &d1);
+        if !g.is_zero() {
+            n1 = n1 / g.clone();
+            d1 = d1 / g.clone();
+        }
+
+        // Close enough?
+        let (n_f, d_f) = match (<F as NumCast>::from(n), <F as NumCast>::from(d)) {
+            (Some(n_f), Some(d_f)) => (n_f, d_f),
+            _ => break,
+        };
+        if (n_f / d_f - val).abs() < max_error {
+            break;
+        }
+
+        // Prevent division by ~0
+        if f < epsilon {
+            break;
+        }
+        q = f.recip();
+    }
+
+    // Overflow
+    if d1.is_zero() {
+        return None;
+    }
+
+    Some(Ratio::new(n1, d1))
+}
+
+macro_rules! as_float_impl {
+    ($($float:ty)*) => ($(
+        /// Converts each part with `as` and divides, so this is as lossy as `as` casts.
+        /// `ToPrimitive` gives a correctly rounded result instead.
+        impl<T> AsPrimitive<$float> for Ratio<T>
+        where
+            T: AsPrimitive<$float>,
+        {
+            #[inline]
+            fn as_(self) -> $float {
+                self.numer.as_() / self.denom.as_()
+            }
+        }
+    )*)
+}
+
+as_float_impl!(f32 f64);
+
+#[cfg(not(feature = "num-bigint"))]
+macro_rules! to_primitive_small {
+    ($($type_name:ty)*) => ($(
+        impl ToPrimitive for Ratio<$type_name> {
+            fn to_i64(&self) -> Option<i64> {
+                self.to_integer().to_i64()
+            }
+
+            fn to_i128(&self) -> Option<i128> {
+                self.to_integer().to_i128()
+            }
+
+            fn to_u64(&self) -> Option<u64> {
+                self.to_integer().to_u64()
+            }
+
+            fn to_u128(&self) -> Option<u128> {
+                self.to_integer().to_u128()
+            }
+
+            fn to_f64(&self) -> Option<f64> {
+                let float = self.numer.to_f64().unwrap() / self.denom.to_f64().unwrap();
+                if float.is_nan() {
+                    None
+                } else {
+                    Some(float)
+                }
+            }
+
+            fn to_f32(&self) -> Option<f32> {
+                let float = ratio_to_f32(self.numer as i128, self.denom as i128);
+                if float.is_nan() {
+                    None
+                } else {
+                    Some(float)
+                }
+            }
+        }
+    )*)
+}
+
+#[cfg(not(feature = "num-bigint"))]
+to_primitive_small!(u8 i8 u16 i16 u32 i32);
+
+#[cfg(all(target_pointer_width = "32", not(feature = "num-bigint")))]
+to_primitive_small!(usize isize);
+
+#[cfg(not(feature = "num-bigint"))]
+macro_rules! to_primitive_64 {
+    ($($type_name:ty)*) => ($(
+        impl ToPrimitive for Ratio<$type_name> {
+            fn to_i64(&self) -> Option<i64> {
+                self.to_integer().to_i64()
+            }
+
+            fn to_i128(&self) -> Option<i128> {
+                self.to_integer().to_i128()
+            }
+
+            fn to_u64(&self) -> Option<u64> {
+                self.to_integer().to_u64()
+            }
+
+            fn to_u128(&self) -> Option<u128> {
+                self.to_integer().to_u128()
+            }
+
+            fn to_f64(&self) -> Option<f64> {
+                let float = ratio_to_f64(
+                    self.numer as i128,
+                    self.denom as i128
+                );
+                if float.is_nan() {
+                    None
+                } else {
+                    Some(float)
+                }
+            }
+
+            fn to_f32(&self) -> Option<f32> {
+                let float = ratio_to_f32(
+                    self.numer as i128,
+                    self.denom as i128
+                );
+                if float.is_nan() {
+                    None
+                } else {
+                    Some(float)
+                }
+            }
+        }
+    )*)
+}
+
+#[cfg(not(feature = "num-bigint"))]
+to_primitive_64!(u64 i64);
+
+#[cfg(all(target_pointer_width = "64", not(feature = "num-bigint")))]
+to_primitive_64!(usize isize);
+
+#[cfg(feature = "num-bigint")]
+impl<T: Clone + Integer + ToPrimitive + ToBigInt> ToPrimitive for Ratio<T> {
+    fn to_i64(&self) -> Option<i64> {
+        self.to_integer().to_i64()
+    }
+
+    fn to_i128(&self) -> Option<i128> {
+        self.to_integer().to_i128()
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        self.to_integer().to_u64()
+    }
+
+    fn to_u128(&self) -> Option<u128> {
+        self.to_integer().to_u128()
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        let float = match (self.numer.to_i64(), self.denom.to_i64()) {
+            (Some(numer), Some(denom)) => ratio_to_f64(
+                <i128 as From<_>>::from(numer),
+                <i128 as From<_>>::from(denom),
+            ),
+            _ => {
+                let numer: BigInt = self.numer.to_bigint()?;
+                let denom: BigInt = self.denom.to_bigint()?;
+                ratio_to_f64(numer, denom)
+            }
+        };
+        if float.is_nan() {
+            None
+        } else {
+            Some(float)
+        }
+    }
+
+    fn to_f32(&self) -> Option<f32> {
+        let float = match (self.numer.to_i64(), self.denom.to_i64()) {
+            (Some(numer), Some(denom)) => ratio_to_f32(
+                <i128 as From<_>>::from(numer),
+                <i128 as From<_>>::from(denom),
+            ),
+            _ => {
+                let numer: BigInt = self.numer.to_bigint()?;
+                let denom: BigInt = self.denom.to_bigint()?;
+                ratio_to_f32(numer, denom)
+            }
+        };
+        if float.is_nan() {
+            None
+        } else {
+            Some(float)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> Ratio<T>
+where
+    Ratio<T>: ToPrimitive,
+{
+    /// Converts a slice of ratios to `f64`s, using `to_f64` on each element.
+    ///
+    /// Elements that can't be converted become `NaN`, so the output always has the same length
+    /// as the input.
+    pub fn to_f64_vec(ratios: &[Ratio<T>]) -> std::vec::Vec<f64> {
+        ratios
+            .iter()
+            .map(|r| r.to_f64().unwrap_or(f64::NAN))
+            .collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Clone + Integer> Ratio<T> {
+    /// Scales all `ratios` to their least common denominator, returning the scaled numerators
+    /// and that denominator.
+    ///
+    /// The ratios are expected to be in lowest terms with positive denominators, as `new`
+    /// produces; the common denominator of an empty slice is one.
+    pub fn clear_denominators(ratios: &[Ratio<T>]) -> (std::vec::Vec<T>, T) {
+        let lcm = ratios.iter().fold(T::one(), |lcm, r| lcm.lcm(&r.denom));
+        let numers = ratios
+            .iter()
+            .map(|r| r.numer.clone() * (lcm.clone() / r.denom.clone()))
+            .collect();
+        (numers, lcm)
+    }
+
+    /// Splits `coeffs` into their content, the gcd of the numerators over the lcm of the
+    /// denominators, and the primitive integer parts, so that each coefficient equals the
+    /// content times its primitive part.
+    ///
+    /// The content is non-negative, and it is zero when all coefficients are zero, in which
+    /// case the primitive parts are all zero too. Like `clear_denominators`, this expects the
+    /// coefficients to be in lowest terms with positive denominators.
+    pub fn content_and_primitive(coeffs: &[Ratio<T>]) -> (Ratio<T>, std::vec::Vec<T>) {
+        let (mut numers, lcm) = Ratio::clear_denominators(coeffs);
+        let gcd = numers.iter().fold(T::zero(), |gcd, n| gcd.gcd(n));
+        if !gcd.is_zero() {
+            for n in &mut numers {
+                *n = n.clone() / gcd.clone();
+            }
+        }
+        (Ratio::new(gcd, lcm), numers)
+    }
+}
+
+/// How to round a value that can't be represented exactly.
+#[cfg(feature = "num-bigint")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RoundingMode {
+    /// Round to the nearest representable value, with ties to even.
+    Nearest,
+    /// Round towards negative infinity.
+    Down,
+    /// Round towards positive infinity.
+    Up,
+    /// Round towards zero.
+    TowardZero,
+}
+
+#[cfg(feature = "num-bigint")]
+impl<T: Clone + Integer + ToPrimitive + ToBigInt> Ratio<T> {
+    /// Converts to an `f64`, rounding in the given direction if the value isn't exactly
+    /// representable.
+    ///
+    /// `RoundingMode::Nearest` gives the same result as `to_f64`. The directed modes round
+    /// values beyond the finite range to infinity or to `±f64::MAX` as appropriate. Returns
+    /// `NaN` where `to_f64` would return `None`.
+    pub fn to_f64_round(&self, mode: RoundingMode) -> f64 {
+        let nearest = match self.to_f64() {
+            Some(f) => f,
+            None => return f64::NAN,
+        };
+        if mode == RoundingMode::Nearest || self.denom.is_zero() {
+            return nearest;
+        }
+        let exact = match (self.numer.to_bigint(), self.denom.to_bigint()) {
+            (Some(numer), Some(denom)) => BigRational::new(numer, denom),
+            _ => return f64::NAN,
+        };
+        let ord = match BigRational::from_float(nearest) {
+            Some(f) => f.cmp(&exact),
+            None if nearest > 0.0 => cmp::Ordering::Greater,
+            None => cmp::Ordering::Less,
+        };
+        let up = match mode {
+            RoundingMode::Up => true,
+            RoundingMode::Down => false,
+            _ => exact.is_negative(),
+        };
+        match ord {
+            cmp::Ordering::Less if up => next_up(nearest),
+            cmp::Ordering::Greater if !up => -next_up(-nearest),
+            _ => nearest,
+        }
+    }
+}
+
+impl<T> Ratio<T>
+where
+    T: Clone + Integer + CheckedMul + CheckedSub + FromPrimitive,
+    Ratio<T>: ToPrimitive,
+{
+    /// Converts to the nearest `f64`, along with the exact rounding error `|self - value|`,
+    /// so that `self` lies within `value ± error`.
+    ///
+    /// The error is computed in `T` with checked arithmetic, from the exact dyadic value of the
+    /// float. That value has up to 53 significant bits, so for fixed-size types it may not fit
+    /// even when the error would. That's why this returns an `Option`: `None` if the nearest
+    /// float is not finite, or if its exact value or the error overflow `T`.
+    pub fn to_f64_with_error(&self) -> Option<(f64, Ratio<T>)> {
+        let value = self.to_f64()?;
+        if !value.is_finite() {
+            return None;
+        }
+
+        // The exact value of the float as `numer / denom`, with `denom` a power of two.
+        let (numer, exponent) = dyadic_parts(value);
+        let numer = T::from_i64(numer)?;
+        let two = T::one() + T::one();
+        let exact = if exponent < 0 {
+            let denom = num_traits::checked_pow(two, exponent.unsigned_abs() as usize)?;
+            Ratio::new_raw(numer, denom)
+        } else {
+            // Doubling the signed numerator step by step reaches `MIN` without overflow.
+            let numer = (0..exponent).try_fold(numer, |n, _| n.checked_mul(&two))?;
+            Ratio::from_integer(numer)
+        };
+
+        // Subtracting the smaller value also keeps unsigned types from going negative.
+        let error = if *self >= exact {
+            self.checked_sub(&exact)?
+        } else {
+            exact.checked_sub(self)?
+        };
+        Some((value, error))
+    }
+}
+
+/// Returns the least `f64` greater than `f`, for non-NaN `f`.
+#[cfg(feature = "num-bigint")]
+fn next_up(f: f64) -> f64 {
+    if f == f64::INFINITY {
+        f
+    } else if f == 0.0 {
+        f64::from_bits(1)
+    } else if f > 0.0 {
+        f64::from_bits(f.to_bits() + 1)
+    } else {
+        f64::from_bits(f.to_bits() - 1)
+    }
+}
+
+trait Bits {
+    fn bits(&self) -> u64;
+}
+
+#[cfg(feature = "num-bigint")]
+impl Bits for BigInt {
+    fn bits(&self) -> u64 {
+        self.bits()
+    }
+}
+
+impl Bits for i128 {
+    fn bits(&self) -> u64 {
+        (128 - self.wrapping_abs().leading_zeros()).into()
+    }
+}
+
+/// Converts a ratio of `T` to an f64.
+///
+/// In addition to stated trait bounds, `T` must be able to hold numbers 56 bits larger than
+/// the largest of `numer` and `denom`. This is automatically true if `T` is `BigInt`.
+fn ratio_to_f64<T: Bits + Clone + Integer + Signed + ShlAssign<usize> + ToPrimitive>(
+    numer: T,
+    denom: T,
+) -> f64 {
+    ratio_to_float(
+        numer,
+        denom,
+        f64::MANTISSA_DIGITS,
+        f64::MIN_EXP,
+        f64::MAX_EXP,
+    )
+}
+
+/// Converts a ratio of `T` to an f32, with the same requirements on `T` as `ratio_to_f64`.
+fn ratio_to_f32<T: Bits + Clone + Integer + Signed + ShlAssign<usize> + ToPrimitive>(
+    numer: T,
+    denom: T,
+) -> f32 {
+    // The result is already rounded to f32 precision and range, so this cast is exact, or
+    // overflows to the correctly rounded infinity.
+    ratio_to_float(
+        numer,
+        denom,
+        f32::MANTISSA_DIGITS,
+        f32::MIN_EXP,
+        f32::MAX_EXP,
+    ) as f32
+}
+
+/// Converts a ratio of `T` to the nearest float with `mantissa_digits` bits of precision and
+/// exponents in `min_exp..=max_exp`, returning that value exactly as an f64.
+#[allow(clippy::eq_op)]
+fn ratio_to_float<T: Bits + Clone + Integer + Signed + ShlAssign<usize> + ToPrimitive>(
+    numer: T,
+    denom: T,
+    mantissa_digits: u32,
+    min_exp: i32,
+    max_exp: i32,
+) -> f64 {
+    const INFINITY: f64 = f64::INFINITY;
+
+    assert_eq!(
+        f64::RADIX,
+        2,
+        "only floating point implementations with radix 2 are supported"
+    );
+
+    // Inclusive upper and lower bounds to the range of exactly-representable ints.
+    let max_exact_int: i64 = 1i64 << mantissa_digits;
+    let min_exact_int: i64 = -max_exact_int;
+
+    let flo_sign = numer.signum().to_f64().unwrap() / denom.signum().to_f64().unwrap();
+    if !flo_sign.is_normal() {
+        return flo_sign;
+    }
+
+    // Fast track: both sides can losslessly be converted to f64s. In this case, letting the
+    // FPU do the job is faster and easier. In any other case, converting to f64s may lead
+    // to an inexact result: https://stackoverflow.com/questions/56641441/.
+    //
+    // Rounding the f64 quotient again to a narrower precision is still correct, since f64 has
+    // more than twice as many mantissa digits as an f32, plus two.
+    if let (Some(n), Some(d)) = (numer.to_i64(), denom.to_i64()) {
+        let exact = min_exact_int..=max_exact_int;
+        if exact.contains(&n) && exact.contains(&d) {
+            return n.to_f64().unwrap() / d.to_f64().unwrap();
         }
     }
+
+    // Otherwise, the goal is to obtain a quotient with at least `mantissa_digits + 2` bits
+    // (55 for an f64). `mantissa_digits` of these bits will be used as the mantissa of the
+    // resulting float, and the remaining two are for rounding. There's an error of up to 1 on
+    // the number of resulting bits, so we may get one more.
+    let mut numer = numer.abs();
+    let mut denom = denom.abs();
+    let (is_diff_positive, absolute_diff) = match numer.bits().checked_sub(denom.bits()) {
+        Some(diff) => (true, diff),
+        None => (false, denom.bits() - numer.bits()),
+    };
+
+    // Filter out overflows and underflows. After this step, the signed difference fits in an
+    // isize.
+    if is_diff_positive && absolute_diff > max_exp as u64 {
+        return INFINITY * flo_sign;
+    }
+    if !is_diff_positive && absolute_diff > -min_exp as u64 + mantissa_digits as u64 + 1 {
+        return 0.0 * flo_sign;
+    }
+    let diff = if is_diff_positive {
+        absolute_diff.to_isize().unwrap()
+    } else {
+        -absolute_diff.to_isize().unwrap()
+    };
+
+    // Shift is chosen so that the quotient will have 2 or 3 bits more than `mantissa_digits`.
+    // The exception is if the quotient is going to be subnormal, in which case it may have
+    // fewer bits.
+    let shift: isize = diff.max(min_exp as isize) - mantissa_digits as isize - 2;
+    if shift >= 0 {
+        denom <<= shift as usize
+    } else {
+        numer <<= -shift as usize
+    };
+
+    let (quotient, remainder) = numer.div_rem(&denom);
+
+    // This is guaranteed to fit since we've set up quotient to be at most 56 bits.
+    let mut quotient = quotient.to_u64().unwrap();
+    let n_rounding_bits = {
+        let quotient_bits = 64 - quotient.leading_zeros() as isize;
+        let subnormal_bits = min_exp as isize - shift;
+        quotient_bits.max(subnormal_bits) - mantissa_digits as isize
+    } as usize;
+    debug_assert!(n_rounding_bits == 2 || n_rounding_bits == 3);
+    let rounding_bit_mask = (1u64 << n_rounding_bits) - 1;
+
+    // Round to `mantissa_digits` bits with round-to-even. For rounding, we need to take into
+    // account both our rounding bits and the division's remainder.
+    let ls_bit = quotient & (1u64 << n_rounding_bits) != 0;
+    let ms_rounding_bit = quotient & (1u64 << (n_rounding_bits - 1)) != 0;
+    let ls_rounding_bits = quotient & (rounding_bit_mask >> 1) != 0;
+    if ms_rounding_bit && (ls_bit || ls_rounding_bits || !remainder.is_zero()) {
+        quotient += 1u64 << n_rounding_bits;
+    }
+    quotient &= !rounding_bit_mask;
+
+    // The quotient is guaranteed to be exactly representable as it's now `mantissa_digits`
+    // bits + 2 or 3 trailing zeros, so there is no risk of a rounding error here.
+    let q_float = quotient as f64 * flo_sign;
+    ldexp(q_float, shift as i32)
 }
 
-#[cfg(feature = "num-bigint")]
-impl FromPrimitive for Ratio<BigInt> {
-    fn from_i64(n: i64) -> Option<Self> {
-        Some(Ratio::from_integer(n.into()))
+/// Multiply `x` by 2 to the power of `exp`. Returns an accurate result even if `2^exp` is not
+/// representable.
+#[allow(clippy::eq_op)]
+fn ldexp(x: f64, exp: i32) -> f64 {
+    const INFINITY: f64 = f64::INFINITY;
+    const MANTISSA_DIGITS: u32 = f64::MANTISSA_DIGITS;
+    const MAX_EXP: i32 = f64::MAX_EXP;
+
+    assert_eq!(
+        f64::RADIX,
+        2,
+        "only floating point implementations with radix 2 are supported"
+    );
+
+    const EXPONENT_MASK: u64 = 0x7ff << 52;
+    const MAX_UNSIGNED_EXPONENT: i32 = 0x7fe;
+    const MIN_SUBNORMAL_POWER: i32 = MANTISSA_DIGITS as i32;
+
+    if x.is_zero() || x.is_infinite() || x.is_nan() {
+        return x;
+    }
+
+    // Filter out obvious over / underflows to make sure the resulting exponent fits in an isize.
+    if exp > 3 * MAX_EXP {
+        return INFINITY * x.signum();
+    } else if exp < -3 * MAX_EXP {
+        return 0.0 * x.signum();
+    }
+
+    // curr_exp is the x's *biased* exponent, and is in the [-54, MAX_UNSIGNED_EXPONENT] range.
+    let (bits, curr_exp) = if !x.is_normal() {
+        // If x is subnormal, we make it normal by multiplying by 2^53. This causes no loss of
+        // precision or rounding.
+        let normal_x = x * 2f64.powi(MIN_SUBNORMAL_POWER);
+        let bits = normal_x.to_bits();
+        // This cast is safe because the exponent is at most 0x7fe, which fits in an i32.
+        (
+            bits,
+            ((bits & EXPONENT_MASK) >> 52) as i32 - MIN_SUBNORMAL_POWER,
+        )
+    } else {
+        let bits = x.to_bits();
+        let curr_exp = (bits & EXPONENT_MASK) >> 52;
+        // This cast is safe because the exponent is at most 0x7fe, which fits in an i32.
+        (bits, curr_exp as i32)
+    };
+
+    // The addition can't overflow because exponent is between 0 and 0x7fe, and exp is between
+    // -2*MAX_EXP and 2*MAX_EXP.
+    let new_exp = curr_exp + exp;
+
+    if new_exp > MAX_UNSIGNED_EXPONENT {
+        INFINITY * x.signum()
+    } else if new_exp > 0 {
+        // Normal case: exponent is not too large nor subnormal.
+        let new_bits = (bits & !EXPONENT_MASK) | ((new_exp as u64) << 52);
+        f64::from_bits(new_bits)
+    } else if new_exp >= -(MANTISSA_DIGITS as i32) {
+        // Result is subnormal but may not be zero.
+        // In this case, we increase the exponent by 54 to make it normal, then multiply the end
+        // result by 2^-53. This results in a single multiplication with no prior rounding error,
+        // so there is no risk of double rounding.
+        let new_exp = new_exp + MIN_SUBNORMAL_POWER;
+        debug_assert!(new_exp >= 0);
+        let new_bits = (bits & !EXPONENT_MASK) | ((new_exp as u64) << 52);
+        f64::from_bits(new_bits) * 2f64.powi(-MIN_SUBNORMAL_POWER)
+    } else {
+        // Result is zero.
+        0.0 * x.signum()
     }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+fn hash<T: Hash>(x: &T) -> u64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::BuildHasher;
+    let mut hasher = <RandomState as BuildHasher>::Hasher::new();
+    x.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+#[allow(clippy::just_underscores_and_digits)]
+mod test {
+    use super::ldexp;
+    #[cfg(feature = "num-bigint")]
+    use super::{BigInt, BigRational};
+    use super::{Ratio, Rational64};
+
+    use core::str::FromStr;
+    use num_integer::Integer;
+    use num_traits::ToPrimitive;
+    use num_traits::{CheckedNeg, FromPrimitive, Num, One, Pow, Signed, Zero};
+
+    pub const _0: Rational64 = Ratio { numer: 0, denom: 1 };
+    pub const _1: Rational64 = Ratio { numer: 1, denom: 1 };
+    pub const _2: Rational64 = Ratio { numer: 2, denom: 1 };
+    pub const _NEG2: Rational64 = Ratio {
+        numer: -2,
+        denom: 1,
+    };
+    pub const _8: Rational64 = Ratio { numer: 8, denom: 1 };
+    pub const _15: Rational64 = Ratio {
+        numer: 15,
+        denom: 1,
+    };
+    pub const _16: Rational64 = Ratio {
+        numer: 16,
+        denom: 1,
+    };
+
+    pub const _1_2: Rational64 = Ratio { numer: 1, denom: 2 };
+    pub const _1_8: Rational64 = Ratio { numer: 1, denom: 8 };
+    pub const _1_15: Rational64 = Ratio {
+        numer: 1,
+        denom: 15,
+    };
+    pub const _1_16: Rational64 = Ratio {
+        numer: 1,
+        denom: 16,
+    };
+    pub const _3_2: Rational64 = Ratio { numer: 3, denom: 2 };
+    pub const _5_2: Rational64 = Ratio { numer: 5, denom: 2 };
+    pub const _NEG1_2: Rational64 = Ratio {
+        numer: -1,
+        denom: 2,
+    };
+    pub const _1_NEG2: Rational64 = Ratio {
+        numer: 1,
+        denom: -2,
+    };
+    pub const _NEG1_NEG2: Rational64 = Ratio {
+        numer: -1,
+        denom: -2,
+    };
+    pub const _1_3: Rational64 = Ratio { numer: 1, denom: 3 };
+    pub const _NEG1_3: Rational64 = Ratio {
+        numer: -1,
+        denom: 3,
+    };
+    pub const _2_3: Rational64 = Ratio { numer: 2, denom: 3 };
+    pub const _NEG2_3: Rational64 = Ratio {
+        numer: -2,
+        denom: 3,
+    };
+    pub const _MIN: Rational64 = Ratio {
+        numer: i64::MIN,
+        denom: 1,
+    };
+    pub const _MIN_P1: Rational64 = Ratio {
+        numer: i64::MIN + 1,
+        denom: 1,
+    };
+    pub const _MAX: Rational64 = Ratio {
+        numer: i64::MAX,
+        denom: 1,
+    };
+    pub const _MAX_M1: Rational64 = Ratio {
+        numer: i64::MAX - 1,
+        denom: 1,
+    };
+    pub const _BILLION: Rational64 = Ratio {
+        numer: 1_000_000_000,
+        denom: 1,
+    };
 
-    fn from_i128(n: i128) -> Option<Self> {
-        Some(Ratio::from_integer(n.into()))
+    #[cfg(feature = "num-bigint")]
+    pub fn to_big(n: Rational64) -> BigRational {
+        Ratio::new(
+            FromPrimitive::from_i64(n.numer).unwrap(),
+            FromPrimitive::from_i64(n.denom).unwrap(),
+        )
     }
-
-    fn from_u64(n: u64) -> Option<Self> {
-        Some(Ratio::from_integer(n.into()))
+    #[cfg(not(feature = "num-bigint"))]
+    pub fn to_big(n: Rational64) -> Rational64 {
+        Ratio::new(
+            FromPrimitive::from_i64(n.numer).unwrap(),
+            FromPrimitive::from_i64(n.denom).unwrap(),
+        )
     }
 
-    fn from_u128(n: u128) -> Option<Self> {
-        Some(Ratio::from_integer(n.into()))
+    #[test]
+    fn test_test_constants() {
+        // check our constants are what Ratio::new etc. would make.
+        assert_eq!(_0, Zero::zero());
+        assert_eq!(_1, One::one());
+        assert_eq!(_2, Ratio::from_integer(2));
+        assert_eq!(_1_2, Ratio::new(1, 2));
+        assert_eq!(_3_2, Ratio::new(3, 2));
+        assert_eq!(_NEG1_2, Ratio::new(-1, 2));
+        assert_eq!(_2, From::from(2));
     }
 
-    fn from_f32(n: f32) -> Option<Self> {
-        Ratio::from_float(n)
-    }
+    #[test]
+    fn test_reduce() {
+        let mut r = Ratio::new_raw(4, 8);
+        r.reduce();
+        assert!(r.strict_eq(&_1_2));
 
-    fn from_f64(n: f64) -> Option<Self> {
-        Ratio::from_float(n)
+        let mut r = Ratio::new_raw(3, -6);
+        r.reduce();
+        assert!(r.strict_eq(&_NEG1_2));
     }
-}
-
-macro_rules! from_primitive_integer {
-    ($typ:ty, $approx:ident) => {
-        impl FromPrimitive for Ratio<$typ> {
-            fn from_i64(n: i64) -> Option<Self> {
-                <$typ as FromPrimitive>::from_i64(n).map(Ratio::from_integer)
-            }
-
-            fn from_i128(n: i128) -> Option<Self> {
-                <$typ as FromPrimitive>::from_i128(n).map(Ratio::from_integer)
-            }
-
-            fn from_u64(n: u64) -> Option<Self> {
-                <$typ as FromPrimitive>::from_u64(n).map(Ratio::from_integer)
-            }
-
-            fn from_u128(n: u128) -> Option<Self> {
-                <$typ as FromPrimitive>::from_u128(n).map(Ratio::from_integer)
-            }
 
-            fn from_f32(n: f32) -> Option<Self> {
-                $approx(n, 10e-20, 30)
-            }
+    #[test]
+    fn test_reduce_shortcuts() {
+        fn test(n: i64, d: i64, expected: (i64, i64)) {
+            let r = Ratio::new(n, d);
+            assert_eq!((r.numer, r.denom), expected);
+        }
 
-            fn from_f64(n: f64) -> Option<Self> {
-                $approx(n, 10e-20, 30)
-            }
+        test(0, 5, (0, 1));
+        test(0, -5, (0, 1));
+        test(7, 1, (7, 1));
+        test(-7, 1, (-7, 1));
+        test(1, 6, (1, 6));
+        test(1, -6, (-1, 6));
+        test(-1, -6, (1, 6));
+        test(4, 4, (1, 1));
+        test(i64::MIN, 1, (i64::MIN, 1));
+
+        assert!(Ratio::new(0, 5).strict_eq(&_0));
+        #[cfg(feature = "num-bigint")]
+        {
+            let r = BigRational::new(BigInt::one(), BigInt::from(-6));
+            assert_eq!(r.into_raw(), (BigInt::from(-1), BigInt::from(6)));
         }
-    };
-}
+    }
 
-from_primitive_integer!(i8, approximate_float);
-from_primitive_integer!(i16, approximate_float);
-from_primitive_integer!(i32, approximate_float);
-from_primitive_integer!(i64, approximate_float);
-from_primitive_integer!(i128, approximate_float);
-from_primitive_integer!(isize, approximate_float);
+    #[test]
+    fn test_reduce_get_gcd() {
+        fn test(n: i64, d: i64, gcd: i64, expected: (i64, i64)) {
+            let mut r = Ratio::new_raw(n, d);
+            assert_eq!(r.reduce_get_gcd(), gcd);
+            assert_eq!(r.into_raw(), expected);
+        }
 
-from_primitive_integer!(u8, approximate_float_unsigned);
-from_primitive_integer!(u16, approximate_float_unsigned);
-from_primitive_integer!(u32, approximate_float_unsigned);
-from_primitive_integer!(u64, approximate_float_unsigned);
-from_primitive_integer!(u128, approximate_float_unsigned);
-from_primitive_integer!(usize, approximate_float_unsigned);
+        test(4, 8, 4, (1, 2));
+        test(-4, -8, 4, (1, 2));
+        test(6, -4, 2, (-3, 2));
+        test(3, 7, 1, (3, 7));
+        test(1, -6, 1, (-1, 6));
+        test(0, -5, 5, (0, 1));
+        test(i64::MIN, 2, 2, (i64::MIN / 2, 1));
 
-impl<T: Integer + Signed + Bounded + NumCast + Clone> Ratio<T> {
-    pub fn approximate_float<F: FloatCore + NumCast>(f: F) -> Option<Ratio<T>> {
-        // 1/10e-20 < 1/2**32 which seems like a good default, and 30 seems
-        // to work well. Might want to choose something based on the types in the future, e.g.
-        // T::max().recip() and T::bits() or something similar.
-        let epsilon = <F as NumCast>::from(10e-20).expect("Can't convert 10e-20");
-        approximate_float(f, epsilon, 30)
+        let mut r = Ratio::new_raw(12u8, 18);
+        assert_eq!(r.reduce_get_gcd(), 6);
+        assert!(r.strict_eq(&Ratio::new(2, 3)));
     }
-}
 
-impl<T: Integer + Unsigned + Bounded + NumCast + Clone> Ratio<T> {
-    pub fn approximate_float_unsigned<F: FloatCore + NumCast>(f: F) -> Option<Ratio<T>> {
-        // 1/10e-20 < 1/2**32 which seems like a good default, and 30 seems
-        // to work well. Might want to choose something based on the types in the future, e.g.
-        // T::max().recip() and T::bits() or something similar.
-        let epsilon = <F as NumCast>::from(10e-20).expect("Can't convert 10e-20");
-        approximate_float_unsigned(f, epsilon, 30)
+    #[test]
+    #[should_panic(expected = "denominator == 0")]
+    fn test_reduce_zero() {
+        Ratio::new_raw(1, 0).reduce();
     }
-}
-
-fn approximate_float<T, F>(val: F, max_error: F, max_iterations: usize) -> Option<Ratio<T>>
-where
-    T: Integer + Signed + Bounded + NumCast + Clone,
-    F: FloatCore + NumCast,
-{
-    let negative = val.is_sign_negative();
-    let abs_val = val.abs();
-
-    let r = approximate_float_unsigned(abs_val, max_error, max_iterations)?;
 
-    // Make negative again if needed
-    Some(if negative { r.neg() } else { r })
-}
+    #[test]
+    fn test_try_reduce() {
+        fn test(n: i64, d: i64, changed: bool, expected: Rational64) {
+            let mut r = Ratio::new_raw(n, d);
+            assert_eq!(r.try_reduce(), changed);
+            assert!(r.strict_eq(&expected));
+        }
 
-// No Unsigned constraint because this also works on positive integers and is called
-// like that, see above
-fn approximate_float_unsigned<T, F>(val: F, max_error: F, max_iterations: usize) -> Option<Ratio<T>>
-where
-    T: Integer + Bounded + NumCast + Clone,
-    F: FloatCore + NumCast,
-{
-    // Continued fractions algorithm
-    // https://web.archive.org/web/20200629111319/http://mathforum.org:80/dr.math/faq/faq.fractions.html#decfrac
+        test(1, 2, false, _1_2);
+        test(0, 1, false, _0);
+        test(-3, 2, false, Ratio::new(-3, 2));
+        test(2, 4, true, _1_2);
+        test(1, -2, true, _NEG1_2);
+        test(-1, -2, true, _1_2);
+        test(0, 5, true, _0);
+        test(6, 6, true, _1);
+        test(i64::MIN, i64::MIN, true, _1);
+        test(0, i64::MIN + 1, true, _0);
+        test(4, -6, true, Ratio::new(-2, 3));
 
-    if val < F::zero() || val.is_nan() {
-        return None;
+        let mut r = _3_2;
+        assert!(!r.try_reduce());
+        assert!(!r.try_reduce());
     }
 
-    let mut q = val;
-    let mut n0 = T::zero();
-    let mut d0 = T::one();
-    let mut n1 = T::one();
-    let mut d1 = T::zero();
-
-    let t_max = T::max_value();
-    let t_max_f = <F as NumCast>::from(t_max.clone())?;
-
-    // 1/epsilon > T::MAX
-    let epsilon = t_max_f.recip();
-
-    // Overflow
-    if q > t_max_f {
-        return None;
+    #[test]
+    #[should_panic(expected = "denominator == 0")]
+    fn test_try_reduce_zero() {
+        let _ = Ratio::new_raw(1, 0).try_reduce();
     }
 
-    for _ in 0..max_iterations {
-        let a = match <T as NumCast>::from(q) {
-            None => break,
-            Some(a) => a,
-        };
+    #[test]
+    fn test_checked() {
+        use crate::ZeroDenominator;
 
-        let a_f = match <F as NumCast>::from(a.clone()) {
-            None => break,
-            Some(a_f) => a_f,
-        };
-        let f = q - a_f;
+        assert_eq!(Ratio::checked(2, 4), Ok(_1_2));
+        assert_eq!(Ratio::checked(1, -2), Ok(_NEG1_2));
+        assert_eq!(Ratio::checked(0, -5), Ok(_0));
+        assert_eq!(Ratio::<i64>::checked(1, 0), Err(ZeroDenominator));
+        assert_eq!(Ratio::<u8>::checked(0, 0), Err(ZeroDenominator));
+        assert!(Ratio::checked(1, 2).unwrap().strict_eq(&_1_2));
 
-        // Prevent overflow
-        if !a.is_zero()
-            && (n1 > t_max.clone() / a.clone()
-                || d1 > t_max.clone() / a.clone()
-                || a.clone() * n1.clone() > t_max.clone() - n0.clone()
-                || a.clone() * d1.clone() > t_max.clone() - d0.clone())
+        #[cfg(feature = "std")]
         {
-            break;
+            use std::string::ToString;
+            assert_eq!(ZeroDenominator.to_string(), "zero value denominator");
+            let err: std::boxed::Box<dyn std::error::Error> = ZeroDenominator.into();
+            assert_eq!(err.to_string(), "zero value denominator");
         }
+    }
 
-        let n = a.clone() * n1.clone() + n0.clone();
-        let d = a.clone() * d1.clone() + d0.clone();
+    #[test]
+    #[cfg(feature = "subtle")]
+    fn test_ct_eq() {
+        use subtle::ConstantTimeEq;
+
+        let rs = [
+            _0,
+            _1,
+            _1_2,
+            _NEG1_2,
+            _1_NEG2,
+            _NEG1_NEG2,
+            Ratio::new_raw(2, 4),
+            Ratio::new_raw(0, -3),
+            _MIN,
+            _MAX,
+        ];
+        for a in &rs {
+            for b in &rs {
+                assert_eq!(bool::from(a.ct_eq(b)), a == b);
+            }
+        }
 
-        n0 = n1;
-        d0 = d1;
-        n1 = n.clone();
-        d1 = d.clone();
+        let a = Ratio::new_raw(6u64, 4);
+        assert!(bool::from(a.ct_eq(&Ratio::new(3, 2))));
+        assert!(!bool::from(a.ct_eq(&Ratio::new(2, 3))));
+    }
 
-        // Simplify fraction. Doing so here instead of at the end
-        // allows us to get closer to the target value without overflows
-        let g = Integer::gcd(&n1, &d1);
-        if !g.is_zero() {
-            n1 = n1 / g.clone();
-            d1 = d1 / g.clone();
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde_fields() {
+        use crate::RatioFields;
+        use serde::de::value::{Error, MapDeserializer, SeqDeserializer};
+        use serde::Deserialize;
+
+        fn from_map(entries: &[(&'static str, i64)]) -> Result<Rational64, Error> {
+            let de = MapDeserializer::new(entries.iter().cloned());
+            RatioFields::deserialize(de).map(Ratio::from)
         }
 
-        // Close enough?
-        let (n_f, d_f) = match (<F as NumCast>::from(n), <F as NumCast>::from(d)) {
-            (Some(n_f), Some(d_f)) => (n_f, d_f),
-            _ => break,
-        };
-        if (n_f / d_f - val).abs() < max_error {
-            break;
-        }
+        let r = from_map(&[("numer", 3), ("denom", 4)]).unwrap();
+        assert!(r.strict_eq(&Ratio::new(3, 4)));
+        let r = from_map(&[("denom", -4), ("numer", 2)]).unwrap();
+        assert_eq!(r.into_raw(), (2, -4));
 
-        // Prevent division by ~0
-        if f < epsilon {
-            break;
-        }
-        q = f.recip();
-    }
+        assert!(from_map(&[("numer", 3), ("denom", 0)]).is_err());
+        assert!(from_map(&[("numer", 3)]).is_err());
+        assert!(from_map(&[("numer", 3), ("denom", 4), ("numer", 5)]).is_err());
+        assert!(from_map(&[("numer", 3), ("denominator", 4)]).is_err());
 
-    // Overflow
-    if d1.is_zero() {
-        return None;
-    }
+        let de = SeqDeserializer::<_, Error>::new([1i64, 2].iter().cloned());
+        assert_eq!(RatioFields::deserialize(de).unwrap().0, _1_2);
 
-    Some(Ratio::new(n1, d1))
-}
+        // round trip through JSON
+        let json = serde_json::to_string(&RatioFields(Ratio::new(3, 4))).unwrap();
+        assert_eq!(json, r#"{"numer":3,"denom":4}"#);
+        let fields: RatioFields<i64> = serde_json::from_str(&json).unwrap();
+        assert!(fields.0.strict_eq(&Ratio::new(3, 4)));
+        let fields: RatioFields<i64> = serde_json::from_str(r#"{"denom":-6,"numer":2}"#).unwrap();
+        assert_eq!(fields.0.into_raw(), (2, -6));
+        assert!(serde_json::from_str::<RatioFields<i64>>(r#"{"numer":1,"denom":0}"#).is_err());
+    }
 
-#[cfg(not(feature = "num-bigint"))]
-macro_rules! to_primitive_small {
-    ($($type_name:ty)*) => ($(
-        impl ToPrimitive for Ratio<$type_name> {
-            fn to_i64(&self) -> Option<i64> {
-                self.to_integer().to_i64()
+    #[test]
+    fn test_checked_from_pair() {
+        fn test(numer: i64, denom: i64, expected: Option<Rational64>) {
+            let r = Ratio::checked_from_pair(numer, denom);
+            assert_eq!(r, expected);
+            if let Some(r) = r {
+                assert!(r.strict_eq(&Ratio::new(numer, denom)));
             }
+        }
 
-            fn to_i128(&self) -> Option<i128> {
-                self.to_integer().to_i128()
-            }
+        test(2, 4, Some(_1_2));
+        test(1, -2, Some(_NEG1_2));
+        test(-3, -2, Some(_3_2));
+        test(0, -5, Some(_0));
+        test(7, 7, Some(_1));
+        test(i64::MIN, i64::MIN, Some(_1));
+        test(i64::MIN, -2, Some(Ratio::from(1 << 62)));
+        test(i64::MIN, 1, Some(_MIN));
+        test(1, 0, None);
+        test(0, 0, None);
 
-            fn to_u64(&self) -> Option<u64> {
-                self.to_integer().to_u64()
-            }
+        // Regression: reducing this pair would need `-i64::MIN`.
+        test(i64::MIN, -1, None);
+        assert_eq!(Ratio::checked_from_pair(1, i64::MIN), None);
 
-            fn to_u128(&self) -> Option<u128> {
-                self.to_integer().to_u128()
-            }
+        assert_eq!(Ratio::checked_from_pair(6u8, 255), Some(Ratio::new(2, 85)));
+    }
 
-            fn to_f64(&self) -> Option<f64> {
-                let float = self.numer.to_f64().unwrap() / self.denom.to_f64().unwrap();
-                if float.is_nan() {
-                    None
-                } else {
-                    Some(float)
-                }
-            }
-        }
-    )*)
-}
+    #[test]
+    fn test_parts() {
+        let (n, d) = _NEG1_2.parts();
+        assert_eq!((*n, *d), (-1, 2));
+        assert_eq!(_3_2.parts(), (_3_2.numer(), _3_2.denom()));
+    }
 
-#[cfg(not(feature = "num-bigint"))]
-to_primitive_small!(u8 i8 u16 i16 u32 i32);
+    #[test]
+    fn test_new_reduce() {
+        assert_eq!(Ratio::new(2, 2), One::one());
+        assert_eq!(Ratio::new(0, i32::MIN), Zero::zero());
+        assert_eq!(Ratio::new(i32::MIN, i32::MIN), One::one());
+    }
+    #[test]
+    #[should_panic]
+    fn test_new_zero() {
+        let _a = Ratio::new(1, 0);
+    }
 
-#[cfg(all(target_pointer_width = "32", not(feature = "num-bigint")))]
-to_primitive_small!(usize isize);
+    #[test]
+    fn test_new_with_reduce() {
+        let r = Ratio::new_with_reduce(4, -8, true);
+        assert_eq!((r.numer, r.denom), (-1, 2));
+        let r = Ratio::new_with_reduce(4, -8, false);
+        assert_eq!((r.numer, r.denom), (4, -8));
+        assert_eq!(r, _NEG1_2);
+    }
 
-#[cfg(not(feature = "num-bigint"))]
-macro_rules! to_primitive_64 {
-    ($($type_name:ty)*) => ($(
-        impl ToPrimitive for Ratio<$type_name> {
-            fn to_i64(&self) -> Option<i64> {
-                self.to_integer().to_i64()
-            }
+    #[test]
+    #[should_panic(expected = "denominator == 0")]
+    fn test_new_with_reduce_zero() {
+        let _a = Ratio::new_with_reduce(1, 0, false);
+    }
+
+    #[test]
+    fn test_new_raw_debug_checked() {
+        let a = Ratio::new_raw_debug_checked(2, 4);
+        assert_eq!((a.numer, a.denom), (2, 4));
+        assert_eq!(Ratio::new_raw_debug_checked(-1, 2), _NEG1_2);
+    }
 
-            fn to_i128(&self) -> Option<i128> {
-                self.to_integer().to_i128()
-            }
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "denominator == 0")]
+    fn test_new_raw_debug_checked_zero() {
+        let _a = Ratio::new_raw_debug_checked(1, 0);
+    }
 
-            fn to_u64(&self) -> Option<u64> {
-                self.to_integer().to_u64()
-            }
+    #[test]
+    fn test_cmp_zero() {
+        use core::cmp::Ordering;
 
-            fn to_u128(&self) -> Option<u128> {
-                self.to_integer().to_u128()
-            }
+        for r in &[_0, _1_2, _NEG1_2, _1_NEG2, _NEG1_NEG2, _MIN, _MAX, _MIN_P1] {
+            assert_eq!(r.cmp_zero(), r.cmp(&_0));
+        }
+        assert_eq!(_3_2.cmp_zero(), Ordering::Greater);
+        assert_eq!(_NEG2.cmp_zero(), Ordering::Less);
+        assert_eq!(_0.cmp_zero(), Ordering::Equal);
+        assert_eq!(Ratio::new_raw(0, -3).cmp_zero(), Ordering::Equal);
+        assert_eq!(_1_NEG2.cmp_zero(), Ordering::Less);
+        assert_eq!(_NEG1_NEG2.cmp_zero(), Ordering::Greater);
+        assert_eq!(Ratio::new(3u8, 4).cmp_zero(), Ordering::Greater);
+    }
 
-            fn to_f64(&self) -> Option<f64> {
-                let float = ratio_to_f64(
-                    self.numer as i128,
-                    self.denom as i128
-                );
-                if float.is_nan() {
-                    None
-                } else {
-                    Some(float)
-                }
+    #[test]
+    fn test_abs_cmp() {
+        use core::cmp::Ordering::*;
+
+        assert_eq!(_3_2.abs_cmp(&_1), Greater);
+        assert_eq!(Ratio::new(-3, 2).abs_cmp(&_1), Greater);
+        assert_eq!(_1.abs_cmp(&Ratio::new(-3, 2)), Less);
+        assert_eq!(_NEG1_2.abs_cmp(&_1_2), Equal);
+        assert_eq!(_1_NEG2.abs_cmp(&_NEG1_NEG2), Equal);
+        assert_eq!(_NEG2.abs_cmp(&_NEG1_2), Greater);
+        assert_eq!(_0.abs_cmp(&_NEG1_2), Less);
+        assert_eq!(_NEG1_2.abs_cmp(&_0), Greater);
+        assert_eq!(_0.abs_cmp(&Ratio::new_raw(0, -3)), Equal);
+
+        // |MIN| can't be computed, but it can be compared
+        assert_eq!(_MIN.abs_cmp(&_MAX), Greater);
+        assert_eq!(_MAX.abs_cmp(&_MIN), Less);
+        assert_eq!(_MIN_P1.abs_cmp(&_MAX), Equal);
+        assert_eq!(
+            Ratio::new_raw(1, i64::MIN).abs_cmp(&Ratio::new(1, i64::MAX)),
+            Less
+        );
+
+        let rs = [_0, _1_2, _NEG1_2, _3_2, _NEG2, _1_3, _MIN_P1];
+        for a in &rs {
+            for b in &rs {
+                assert_eq!(a.abs_cmp(b), a.abs().cmp(&b.abs()));
             }
         }
-    )*)
-}
 
-#[cfg(not(feature = "num-bigint"))]
-to_primitive_64!(u64 i64);
+        assert_eq!(Ratio::new(1u8, 3).abs_cmp(&Ratio::new(1, 2)), Less);
+    }
 
-#[cfg(all(target_pointer_width = "64", not(feature = "num-bigint")))]
-to_primitive_64!(usize isize);
+    #[test]
+    fn test_is_between() {
+        assert!(_1_2.is_between(&_0, &_1, true));
+        assert!(_1_2.is_between(&_0, &_1, false));
+        assert!(!_3_2.is_between(&_0, &_1, true));
+        assert!(!_NEG1_2.is_between(&_0, &_1, true));
 
-#[cfg(feature = "num-bigint")]
-impl<T: Clone + Integer + ToPrimitive + ToBigInt> ToPrimitive for Ratio<T> {
-    fn to_i64(&self) -> Option<i64> {
-        self.to_integer().to_i64()
+        // endpoints
+        assert!(_0.is_between(&_0, &_1, true));
+        assert!(_1.is_between(&_0, &_1, true));
+        assert!(!_0.is_between(&_0, &_1, false));
+        assert!(!_1.is_between(&_0, &_1, false));
+        assert!(_1_2.is_between(&Ratio::new_raw(2, 4), &_1_2, true));
+        assert!(!_1_2.is_between(&_1_2, &_1_2, false));
+
+        // empty ranges
+        assert!(!_1_2.is_between(&_1, &_0, true));
+        assert!(!_1_2.is_between(&_1, &_0, false));
     }
 
-    fn to_i128(&self) -> Option<i128> {
-        self.to_integer().to_i128()
+    #[test]
+    fn test_clamp_denominator() {
+        // already within the bound, so unchanged
+        assert_eq!(_1_3.clamp_denominator(&3), _1_3);
+        assert_eq!(
+            Ratio::new_raw(2, 4).clamp_denominator(&4).into_raw(),
+            (2, 4)
+        );
+        assert!(Ratio::new_raw(2, 4).clamp_denominator(&2).strict_eq(&_1_2));
+        assert!(Ratio::new_raw(1, -2)
+            .clamp_denominator(&2)
+            .strict_eq(&_NEG1_2));
+
+        // approximating
+        let pi: Rational64 = Ratio::new(3141592653589793, 1000000000000000);
+        assert_eq!(pi.clamp_denominator(&10), Ratio::new(22, 7));
+        assert_eq!(pi.clamp_denominator(&100), Ratio::new(311, 99));
+        assert_eq!(pi.clamp_denominator(&1000), Ratio::new(355, 113));
+        assert_eq!((-pi).clamp_denominator(&1000), Ratio::new(-355, 113));
+        assert_eq!(pi.clamp_denominator(&1), Ratio::from(3));
+        assert_eq!(Ratio::new(7u32, 10).clamp_denominator(&3), Ratio::new(2, 3));
+        assert_eq!(Ratio::new(1, 1000).clamp_denominator(&10), _0);
+        assert_eq!(Ratio::new(999, 1000).clamp_denominator(&10), _1);
+        assert_eq!(
+            Ratio::new(i64::MAX - 1, i64::MAX).clamp_denominator(&1000),
+            _1
+        );
+
+        #[cfg(feature = "num-bigint")]
+        assert_eq!(
+            to_big(pi).clamp_denominator(&BigInt::from(1000)),
+            to_big(Ratio::new(355, 113))
+        );
     }
 
-    fn to_u64(&self) -> Option<u64> {
-        self.to_integer().to_u64()
+    #[test]
+    #[should_panic(expected = "max_denom < 1")]
+    fn test_clamp_denominator_zero() {
+        let _ = _1_3.clamp_denominator(&0);
     }
 
-    fn to_u128(&self) -> Option<u128> {
-        self.to_integer().to_u128()
+    #[test]
+    fn test_approximations() {
+        let target = Ratio::new(355, 113);
+        let mut approx = Ratio::approximations(&target, 1000);
+        assert_eq!(approx.next(), Some(Ratio::from(3)));
+        assert_eq!(approx.nth(3), Some(Ratio::new(22, 7)));
+        assert_eq!(approx.last(), Some(target));
+        assert_eq!(
+            Ratio::approximations(&target, 7).last(),
+            Some(Ratio::new(22, 7))
+        );
+        assert_eq!(Ratio::approximations(&_MAX, 1).last(), Some(_MAX));
+
+        // the distinct values of clamp_denominator as the bound increases
+        for numer in -30..=30 {
+            for denom in 1..=30 {
+                let r = Ratio::new(numer, denom);
+                let mut approx = Ratio::approximations(&r, 20);
+                let mut prev = None;
+                for q in 1..=20 {
+                    let clamped = r.clamp_denominator(&q);
+                    if prev != Some(clamped) {
+                        assert_eq!(approx.next(), Some(clamped), "{} at {}", r, q);
+                        prev = Some(clamped);
+                    }
+                }
+                assert_eq!(approx.next(), None);
+            }
+        }
     }
 
-    fn to_f64(&self) -> Option<f64> {
-        let float = match (self.numer.to_i64(), self.denom.to_i64()) {
-            (Some(numer), Some(denom)) => ratio_to_f64(
-                <i128 as From<_>>::from(numer),
-                <i128 as From<_>>::from(denom),
-            ),
-            _ => {
-                let numer: BigInt = self.numer.to_bigint()?;
-                let denom: BigInt = self.denom.to_bigint()?;
-                ratio_to_f64(numer, denom)
+    #[test]
+    fn test_continued_fraction() {
+        assert!(Ratio::new(415, 93).continued_fraction().eq([4, 2, 6, 7]));
+        assert!(Ratio::new(-7, 3).continued_fraction().eq([-3, 1, 2]));
+        assert!(Ratio::<i64>::new_raw(7, -3)
+            .continued_fraction()
+            .eq([-3, 1, 2]));
+        assert!(_1_2.continued_fraction().eq([0, 2]));
+        assert!(_0.continued_fraction().eq([0]));
+        assert!(_MIN.continued_fraction().eq([i64::MIN]));
+        assert!(Ratio::new(i64::MAX, i64::MAX - 1)
+            .continued_fraction()
+            .eq([1, i64::MAX - 1]));
+
+        assert_eq!(
+            Ratio::from_continued_fraction([4, 2, 6, 7]),
+            Ratio::new(415, 93)
+        );
+        assert_eq!(
+            Ratio::from_continued_fraction([-3, 1, 2]),
+            Ratio::new(-7, 3)
+        );
+        // a trailing one is the non-canonical form of the same value
+        assert_eq!(Ratio::from_continued_fraction([0, 1, 1]), _1_2);
+        assert_eq!(Ratio::from_continued_fraction([i64::MIN]), _MIN);
+
+        for numer in -50..=50 {
+            for denom in 1..=50 {
+                let r = Ratio::new(numer, denom);
+                assert_eq!(Ratio::from_continued_fraction(r.continued_fraction()), r);
             }
-        };
-        if float.is_nan() {
-            None
-        } else {
-            Some(float)
+        }
+        for &r in &[
+            _MAX,
+            _MIN,
+            Ratio::new(i64::MAX, i64::MAX - 1),
+            Ratio::new(-1, i64::MAX),
+        ] {
+            assert_eq!(Ratio::from_continued_fraction(r.continued_fraction()), r);
         }
     }
-}
 
-trait Bits {
-    fn bits(&self) -> u64;
-}
+    #[test]
+    fn test_convergents() {
+        let r = Ratio::new(415, 93);
+        let expected = [Ratio::from(4), Ratio::new(9, 2), Ratio::new(58, 13), r];
+        assert!(r.convergents().eq(expected));
+        assert!(Ratio::new(-7, 3)
+            .convergents()
+            .eq([Ratio::from(-3), -_2, Ratio::new(-7, 3)]));
+        assert!(_MAX.convergents().eq([_MAX]));
+
+        for numer in -50..=50 {
+            for denom in 1..=50 {
+                let r = Ratio::new(numer, denom);
+                assert_eq!(r.convergents().last(), Some(r));
+                // even-indexed convergents are below r, odd-indexed ones above, except the last
+                for (k, c) in r.convergents().enumerate() {
+                    assert!(c.denom > 0 && c.numer.gcd(&c.denom) == 1);
+                    if c == r {
+                        break;
+                    }
+                    assert_eq!(c < r, k % 2 == 0);
+                }
+            }
+        }
 
-#[cfg(feature = "num-bigint")]
-impl Bits for BigInt {
-    fn bits(&self) -> u64 {
-        self.bits()
+        #[cfg(feature = "num-bigint")]
+        {
+            let pi: BigRational = "3141592653589793238462643383279/1000000000000000000000000000000"
+                .parse()
+                .unwrap();
+            let bound = BigInt::from(1000);
+            let best = pi.convergents().take_while(|c| c.denom <= bound).last();
+            assert_eq!(best, Some(to_big(Ratio::new(355, 113))));
+            assert_eq!(pi.convergents().last(), Some(pi));
+        }
     }
-}
 
-impl Bits for i128 {
-    fn bits(&self) -> u64 {
-        (128 - self.wrapping_abs().leading_zeros()).into()
+    #[test]
+    #[should_panic(expected = "empty continued fraction")]
+    fn test_from_continued_fraction_empty() {
+        let _ = Rational64::from_continued_fraction([]);
     }
-}
 
-/// Converts a ratio of `T` to an f64.
-///
-/// In addition to stated trait bounds, `T` must be able to hold numbers 56 bits larger than
-/// the largest of `numer` and `denom`. This is automatically true if `T` is `BigInt`.
-fn ratio_to_f64<T: Bits + Clone + Integer + Signed + ShlAssign<usize> + ToPrimitive>(
-    numer: T,
-    denom: T,
-) -> f64 {
-    use core::f64::{INFINITY, MANTISSA_DIGITS, MAX_EXP, MIN_EXP, RADIX};
+    #[test]
+    fn test_midpoint() {
+        assert_eq!(_0.midpoint(&_1), _1_2);
+        assert_eq!(_1.midpoint(&_0), _1_2);
+        assert_eq!(_NEG1_2.midpoint(&_3_2), _1_2);
+        assert_eq!(_2.midpoint(&_2), _2);
+        // the sum of either pair would overflow
+        let max_m2 = Ratio::from(i64::MAX - 2);
+        assert_eq!(_MAX.midpoint(&max_m2), _MAX_M1);
+        let min_p2 = Ratio::from(i64::MIN + 2);
+        assert_eq!(min_p2.midpoint(&_MIN), _MIN_P1);
+        // the difference would overflow
+        assert_eq!(_MIN.midpoint(&_MAX), Ratio::new(-1, 2));
+        assert_eq!(_MAX.midpoint(&_MIN), Ratio::new(-1, 2));
+        assert_eq!(_MIN_P1.midpoint(&_MAX), _0);
+        assert_eq!(_MIN.midpoint(&_1), Ratio::new(i64::MIN + 1, 2));
+        assert_eq!(
+            Ratio::new(-1, i64::MAX).midpoint(&Ratio::new(1, i64::MAX)),
+            _0
+        );
+    }
 
-    assert_eq!(
-        RADIX, 2,
-        "only floating point implementations with radix 2 are supported"
-    );
+    #[test]
+    fn test_simplest_within() {
+        let tol = Ratio::new(1, 100);
+        assert_eq!(
+            Ratio::new(157, 500).simplest_within(&tol),
+            Ratio::new(4, 13)
+        );
+        assert_eq!(
+            Ratio::new(-157, 500).simplest_within(&tol),
+            Ratio::new(-4, 13)
+        );
+        assert_eq!(
+            Ratio::new(355, 113).simplest_within(&Ratio::new(1, 1000)),
+            Ratio::new(201, 64)
+        );
 
-    // Inclusive upper and lower bounds to the range of exactly-representable ints in an f64.
-    const MAX_EXACT_INT: i64 = 1i64 << MANTISSA_DIGITS;
-    const MIN_EXACT_INT: i64 = -MAX_EXACT_INT;
+        // the bounds are included
+        assert_eq!(Ratio::new(3, 5).simplest_within(&Ratio::new(1, 10)), _1_2);
+        assert_eq!(_NEG1_2.simplest_within(&_1_2), _0);
+        assert_eq!(_3_2.simplest_within(&_1_2), _1);
+        assert_eq!(Ratio::new(7, 3).simplest_within(&_1_2), _2);
+        assert_eq!(_1_3.simplest_within(&_0), _1_3);
 
-    let flo_sign = numer.signum().to_f64().unwrap() / denom.signum().to_f64().unwrap();
-    if !flo_sign.is_normal() {
-        return flo_sign;
+        assert_eq!(
+            Ratio::new(3u8, 8).simplest_within(&Ratio::new(1, 20)),
+            Ratio::new(1, 3)
+        );
     }
 
-    // Fast track: both sides can losslessly be converted to f64s. In this case, letting the
-    // FPU do the job is faster and easier. In any other case, converting to f64s may lead
-    // to an inexact result: https://stackoverflow.com/questions/56641441/.
-    if let (Some(n), Some(d)) = (numer.to_i64(), denom.to_i64()) {
-        let exact = MIN_EXACT_INT..=MAX_EXACT_INT;
-        if exact.contains(&n) && exact.contains(&d) {
-            return n.to_f64().unwrap() / d.to_f64().unwrap();
+    #[test]
+    fn test_simplest_between() {
+        fn test(lo: Rational64, hi: Rational64, expected: Option<Rational64>) {
+            assert_eq!(Ratio::simplest_between(&lo, &hi), expected);
+            assert_eq!(Ratio::simplest_between(&-hi, &-lo), expected.map(|r| -r));
         }
+
+        test(_1_3, _1_2, Some(Ratio::new(2, 5)));
+        test(_0, _1_2, Some(_1_3));
+        test(_0, _1, Some(_1_2));
+        test(_1, _2, Some(Ratio::new(3, 2)));
+        test(_1, Ratio::new(5, 2), Some(_2));
+        test(_1_2, _3_2, Some(_1));
+        test(Ratio::new(3, 1), Ratio::new(22, 7), Some(Ratio::new(25, 8)));
+        test(
+            Ratio::new(314, 100),
+            Ratio::new(315, 100),
+            Some(Ratio::new(22, 7)),
+        );
+        test(_NEG1_2, _1_2, Some(_0));
+
+        test(_1_2, _1_2, None);
+        test(_1_2, _1_3, None);
+
+        assert_eq!(
+            Ratio::simplest_between(&Ratio::new(1u8, 3), &Ratio::new(1, 2)),
+            Some(Ratio::new(2, 5))
+        );
     }
 
-    // Otherwise, the goal is to obtain a quotient with at least 55 bits. 53 of these bits will
-    // be used as the mantissa of the resulting float, and the remaining two are for rounding.
-    // There's an error of up to 1 on the number of resulting bits, so we may get either 55 or
-    // 56 bits.
-    let mut numer = numer.abs();
-    let mut denom = denom.abs();
-    let (is_diff_positive, absolute_diff) = match numer.bits().checked_sub(denom.bits()) {
-        Some(diff) => (true, diff),
-        None => (false, denom.bits() - numer.bits()),
-    };
+    #[test]
+    #[should_panic(expected = "tol < 0")]
+    fn test_simplest_within_negative_tol() {
+        let _ = _1_2.simplest_within(&_NEG1_2);
+    }
 
-    // Filter out overflows and underflows. After this step, the signed difference fits in an
-    // isize.
-    if is_diff_positive && absolute_diff > MAX_EXP as u64 {
-        return INFINITY * flo_sign;
+    #[test]
+    fn test_midpoint_unsigned() {
+        let max = Ratio::from(u32::MAX);
+        let max_m2 = Ratio::from(u32::MAX - 2);
+        assert_eq!(max.midpoint(&max_m2), Ratio::from(u32::MAX - 1));
+        assert_eq!(max_m2.midpoint(&max), Ratio::from(u32::MAX - 1));
+
+        let zero = Ratio::from(0);
+        assert_eq!(zero.midpoint(&max), Ratio::new(u32::MAX, 2));
+        assert_eq!(max.midpoint(&zero), Ratio::new(u32::MAX, 2));
+
+        let half_max = Ratio::new(u32::MAX, 2);
+        let half_max_m2 = Ratio::new(u32::MAX - 2, 2);
+        assert_eq!(half_max.midpoint(&half_max_m2), Ratio::new(u32::MAX - 1, 2));
+        assert_eq!(max.midpoint(&max), max);
     }
-    if !is_diff_positive && absolute_diff > -MIN_EXP as u64 + MANTISSA_DIGITS as u64 + 1 {
-        return 0.0 * flo_sign;
+
+    #[test]
+    fn test_cmp_dist() {
+        use core::cmp::Ordering::*;
+
+        let mut candidates = [
+            _1,
+            _0,
+            _1_2,
+            Ratio::new(2, 5),
+            _NEG2,
+            _1_3,
+            Ratio::new(3, 10),
+        ];
+        candidates.sort_unstable_by(|a, b| _1_3.cmp_dist(a, b));
+        let expected = [
+            _1_3,
+            Ratio::new(3, 10),
+            Ratio::new(2, 5),
+            _1_2,
+            _0,
+            _1,
+            _NEG2,
+        ];
+        assert_eq!(candidates, expected);
+
+        // 1/4 and 5/12 are both 1/12 away
+        assert_eq!(_1_3.cmp_dist(&Ratio::new(1, 4), &Ratio::new(5, 12)), Equal);
+        assert_eq!(_1_3.cmp_dist(&_1_3, &_1_3), Equal);
+
+        let rs = [_0, _1_3, _NEG1_2, _3_2, Ratio::new(1, 4), Ratio::new(5, 12)];
+        for x in &rs {
+            for a in &rs {
+                for b in &rs {
+                    let expected = (x - a).abs().cmp(&(x - b).abs());
+                    assert_eq!(x.cmp_dist(a, b), expected);
+                }
+            }
+        }
+
+        // Both on the same side, so `MAX - MIN` is never computed
+        assert_eq!(_MAX.cmp_dist(&_MIN, &_MAX_M1), Greater);
+        assert_eq!(_MIN.cmp_dist(&_MIN_P1, &_MAX), Less);
+        assert_eq!(
+            Ratio::new(1u8, 3).cmp_dist(&Ratio::new(1, 4), &Ratio::new(1, 2)),
+            Less
+        );
+
+        // On opposite sides, so this compares with the midpoint -1/2
+        assert_eq!(_0.cmp_dist(&_MIN, &_MAX), Greater);
+        assert_eq!(_0.cmp_dist(&_MAX, &_MIN), Less);
+        assert_eq!(_NEG1_2.cmp_dist(&_MIN, &_MAX), Equal);
+        assert_eq!(_1.cmp_dist(&_MIN_P1, &_MAX), Greater);
+        assert_eq!(_MAX_M1.cmp_dist(&_MIN, &_MAX), Greater);
+        assert_eq!(_MIN_P1.cmp_dist(&_MIN, &_MAX), Less);
     }
-    let diff = if is_diff_positive {
-        absolute_diff.to_isize().unwrap()
-    } else {
-        -absolute_diff.to_isize().unwrap()
-    };
 
-    // Shift is chosen so that the quotient will have 55 or 56 bits. The exception is if the
-    // quotient is going to be subnormal, in which case it may have fewer bits.
-    let shift: isize = diff.max(MIN_EXP as isize) - MANTISSA_DIGITS as isize - 2;
-    if shift >= 0 {
-        denom <<= shift as usize
-    } else {
-        numer <<= -shift as usize
-    };
+    #[test]
+    fn test_mul_add() {
+        let rs = [_0, _1, _2, _1_2, _NEG1_2, _1_3, _3_2, Ratio::new(-5, 6)];
+        for x in &rs {
+            for a in &rs {
+                for b in &rs {
+                    assert_eq!(x.mul_add(a, b), x * a + b);
+                }
+            }
+        }
+        assert!(Ratio::<i64>::new_raw(2, 4)
+            .mul_add(&Ratio::new_raw(3, -6), &Ratio::new_raw(1, 2))
+            .strict_eq(&Ratio::new(1, 4)));
 
-    let (quotient, remainder) = numer.div_rem(&denom);
+        // Horner's method for 2x^3 - x + 1/2 at x = 2/3
+        let x = Ratio::new(2, 3);
+        let coeffs = [_2, _0, -_1, _1_2];
+        let y = coeffs.iter().fold(_0, |acc, c| acc.mul_add(&x, c));
+        assert_eq!(y, Ratio::new(23, 54));
 
-    // This is guaranteed to fit since we've set up quotient to be at most 56 bits.
-    let mut quotient = quotient.to_u64().unwrap();
-    let n_rounding_bits = {
-        let quotient_bits = 64 - quotient.leading_zeros() as isize;
-        let subnormal_bits = MIN_EXP as isize - shift;
-        quotient_bits.max(subnormal_bits) - MANTISSA_DIGITS as isize
-    } as usize;
-    debug_assert!(n_rounding_bits == 2 || n_rounding_bits == 3);
-    let rounding_bit_mask = (1u64 << n_rounding_bits) - 1;
+        // common factors cancel in both steps
+        let big = Ratio::new(i64::MAX, 2);
+        let y = big.mul_add(&big.recip(), &Ratio::new(-1, i64::MAX));
+        assert_eq!(y, Ratio::new(i64::MAX - 1, i64::MAX));
+    }
 
-    // Round to 53 bits with round-to-even. For rounding, we need to take into account both
-    // our rounding bits and the division's remainder.
-    let ls_bit = quotient & (1u64 << n_rounding_bits) != 0;
-    let ms_rounding_bit = quotient & (1u64 << (n_rounding_bits - 1)) != 0;
-    let ls_rounding_bits = quotient & (rounding_bit_mask >> 1) != 0;
-    if ms_rounding_bit && (ls_bit || ls_rounding_bits || !remainder.is_zero()) {
-        quotient += 1u64 << n_rounding_bits;
+    #[test]
+    fn test_cross() {
+        // the triangle (0, 0), (4, 0), (0, 3) has area 6, counter-clockwise
+        let area = |p: [Rational64; 2], q: [Rational64; 2], r: [Rational64; 2]| {
+            Ratio::cross(
+                &(q[0] - p[0]),
+                &(q[1] - p[1]),
+                &(r[0] - p[0]),
+                &(r[1] - p[1]),
+            ) / 2
+        };
+        let (x, y, z) = ([_0, _0], [Ratio::from(4), _0], [_0, Ratio::from(3)]);
+        assert_eq!(area(x, y, z), Ratio::from(6));
+        assert_eq!(area(x, z, y), Ratio::from(-6));
+        assert_eq!(area(x, y, [_2, _0]), _0);
+
+        let (x, y, z) = ([_1_2, _1_3], [_3_2, _1_3], [_1_2, Ratio::new(5, 6)]);
+        assert_eq!(area(x, y, z), Ratio::new(1, 4));
+
+        // the products cancel before multiplying
+        let big = Ratio::new(i64::MAX, 3);
+        assert_eq!(Ratio::cross(&big, &_1, &_2, &big.recip()), -_1);
     }
-    quotient &= !rounding_bit_mask;
 
-    // The quotient is guaranteed to be exactly representable as it's now 53 bits + 2 or 3
-    // trailing zeros, so there is no risk of a rounding error here.
-    let q_float = quotient as f64 * flo_sign;
-    ldexp(q_float, shift as i32)
-}
+    #[test]
+    fn test_bisect() {
+        // x^2 - 2 crosses zero at sqrt(2)
+        let tol = Ratio::new(1, 1000);
+        let root = Ratio::bisect(_1, _2, &tol, |x| (x * x).cmp(&_2));
+        assert!((root * root - _2).abs() < Ratio::new(1, 100));
+        assert!(root > Ratio::new(1414, 1000) - tol && root < Ratio::new(1415, 1000) + tol);
 
-/// Multiply `x` by 2 to the power of `exp`. Returns an accurate result even if `2^exp` is not
-/// representable.
-fn ldexp(x: f64, exp: i32) -> f64 {
-    use core::f64::{INFINITY, MANTISSA_DIGITS, MAX_EXP, RADIX};
+        // decreasing function, with the bounds swapped
+        let root = Ratio::bisect(_2, _0, &tol, |x| _1.cmp(x));
+        assert_eq!(root, _1);
 
-    assert_eq!(
-        RADIX, 2,
-        "only floating point implementations with radix 2 are supported"
-    );
+        let root = Ratio::bisect(_1_2, _2, &tol, |x| x.cmp(&_1_2));
+        assert_eq!(root, _1_2);
+    }
 
-    const EXPONENT_MASK: u64 = 0x7ff << 52;
-    const MAX_UNSIGNED_EXPONENT: i32 = 0x7fe;
-    const MIN_SUBNORMAL_POWER: i32 = MANTISSA_DIGITS as i32;
+    #[test]
+    #[should_panic(expected = "tol <= 0")]
+    fn test_bisect_zero_tol() {
+        let _ = Ratio::bisect(_0, _1, &_0, |x| x.cmp(&_1_2));
+    }
 
-    if x.is_zero() || x.is_infinite() || x.is_nan() {
-        return x;
+    #[test]
+    fn test_abs_sub() {
+        let a = Ratio::new(3u32, 4);
+        let b = Ratio::new(1u32, 3);
+        assert_eq!(a.abs_sub(&b), Ratio::new(5, 12));
+        assert_eq!(b.abs_sub(&a), Ratio::zero());
+        assert_eq!(a.abs_sub(&a), Ratio::zero());
+        assert_eq!(
+            Ratio::new(0u8, 1).abs_sub(&Ratio::new(255, 1)),
+            Ratio::zero()
+        );
+
+        assert_eq!(_3_2.abs_sub(&_NEG1_2), _2);
+        assert_eq!(_NEG1_2.abs_sub(&_3_2), _0);
     }
 
-    // Filter out obvious over / underflows to make sure the resulting exponent fits in an isize.
-    if exp > 3 * MAX_EXP {
-        return INFINITY * x.signum();
-    } else if exp < -3 * MAX_EXP {
-        return 0.0 * x.signum();
+    #[test]
+    fn test_div_ratios() {
+        for (num, den) in &[
+            (_1_2, _3_2),
+            (_NEG1_2, _2_3),
+            (_MAX, _MAX_M1),
+            (_MIN_P1, _MAX),
+        ] {
+            assert_eq!(Ratio::div_ratios(num, den), num / den);
+        }
+        assert_eq!(Ratio::div_ratios(&_3_2, &_3_2), _1);
+        // cross-cancellation keeps the intermediate products in range
+        let a = Ratio::new(i64::MAX, 3);
+        let b = Ratio::new(i64::MAX, 5);
+        assert_eq!(Ratio::div_ratios(&a, &b), Ratio::new(5, 3));
     }
 
-    // curr_exp is the x's *biased* exponent, and is in the [-54, MAX_UNSIGNED_EXPONENT] range.
-    let (bits, curr_exp) = if !x.is_normal() {
-        // If x is subnormal, we make it normal by multiplying by 2^53. This causes no loss of
-        // precision or rounding.
-        let normal_x = x * 2f64.powi(MIN_SUBNORMAL_POWER);
-        let bits = normal_x.to_bits();
-        // This cast is safe because the exponent is at most 0x7fe, which fits in an i32.
-        (
-            bits,
-            ((bits & EXPONENT_MASK) >> 52) as i32 - MIN_SUBNORMAL_POWER,
-        )
-    } else {
-        let bits = x.to_bits();
-        let curr_exp = (bits & EXPONENT_MASK) >> 52;
-        // This cast is safe because the exponent is at most 0x7fe, which fits in an i32.
-        (bits, curr_exp as i32)
-    };
+    #[test]
+    #[should_panic(expected = "division by zero rational")]
+    fn test_div_ratios_zero() {
+        let _r = Ratio::div_ratios(&_1_2, &_0);
+    }
 
-    // The addition can't overflow because exponent is between 0 and 0x7fe, and exp is between
-    // -2*MAX_EXP and 2*MAX_EXP.
-    let new_exp = curr_exp + exp;
+    #[test]
+    fn test_to_scaled_integer() {
+        fn test(r: Rational64, scale: u32, expected: i64) {
+            assert_eq!(r.to_scaled_integer(scale), expected);
+            assert_eq!(r.checked_to_scaled_integer(scale), Some(expected));
+        }
 
-    if new_exp > MAX_UNSIGNED_EXPONENT {
-        INFINITY * x.signum()
-    } else if new_exp > 0 {
-        // Normal case: exponent is not too large nor subnormal.
-        let new_bits = (bits & !EXPONENT_MASK) | ((new_exp as u64) << 52);
-        f64::from_bits(new_bits)
-    } else if new_exp >= -(MANTISSA_DIGITS as i32) {
-        // Result is subnormal but may not be zero.
-        // In this case, we increase the exponent by 54 to make it normal, then multiply the end
-        // result by 2^-53. This results in a single multiplication with no prior rounding error,
-        // so there is no risk of double rounding.
-        let new_exp = new_exp + MIN_SUBNORMAL_POWER;
-        debug_assert!(new_exp >= 0);
-        let new_bits = (bits & !EXPONENT_MASK) | ((new_exp as u64) << 52);
-        f64::from_bits(new_bits) * 2f64.powi(-MIN_SUBNORMAL_POWER)
-    } else {
-        // Result is zero.
-        return 0.0 * x.signum();
+        test(Ratio::new(1, 3), 4, 3333);
+        test(Ratio::new(2, 3), 4, 6667);
+        test(Ratio::new(-2, 3), 4, -6667);
+        test(Ratio::new(1, 8), 2, 13);
+        test(Ratio::new(-1, 8), 2, -13);
+        test(Ratio::new(1, 8), 3, 125);
+        test(_3_2, 0, 2);
+        test(_NEG1_2, 0, -1);
+        test(_0, 10, 0);
+        test(Ratio::new(i64::MAX, 10), 1, i64::MAX);
+
+        assert_eq!(_1.checked_to_scaled_integer(19), None);
+        assert_eq!(Ratio::new(i64::MAX, 1).checked_to_scaled_integer(1), None);
+        assert_eq!(Ratio::new(255u8, 2).checked_to_scaled_integer(0), Some(128));
+        assert_eq!(Ratio::new(253u8, 2).checked_to_scaled_integer(0), Some(127));
+        assert_eq!(
+            Ratio::new(-128i8, 1).checked_to_scaled_integer(0),
+            Some(-128)
+        );
+        assert_eq!(Ratio::new(1u32, 3).checked_to_scaled_integer(100), None);
     }
-}
 
-#[cfg(test)]
-#[cfg(feature = "std")]
-fn hash<T: Hash>(x: &T) -> u64 {
-    use std::collections::hash_map::RandomState;
-    use std::hash::BuildHasher;
-    let mut hasher = <RandomState as BuildHasher>::Hasher::new();
-    x.hash(&mut hasher);
-    hasher.finish()
-}
+    #[test]
+    fn test_from_scaled_integer() {
+        assert_eq!(Ratio::from_scaled_integer(3333, 4), Ratio::new(3333, 10000));
+        assert_eq!(Ratio::from_scaled_integer(2500, 4), Ratio::new(1, 4));
+        assert_eq!(Ratio::from_scaled_integer(-15, 1), _NEG1_2 * 3);
+        assert_eq!(Ratio::from_scaled_integer(7, 0), Ratio::from(7));
+        assert_eq!(Ratio::from_scaled_integer(0, 18), _0);
 
-#[cfg(test)]
-mod test {
-    use super::ldexp;
-    #[cfg(feature = "num-bigint")]
-    use super::{BigInt, BigRational};
-    use super::{Ratio, Rational64};
+        let r = Ratio::new(1234567, 1000);
+        assert_eq!(Ratio::from_scaled_integer(r.to_scaled_integer(3), 3), r);
+    }
 
-    use core::f64;
-    use core::i32;
-    use core::i64;
-    use core::str::FromStr;
-    use num_integer::Integer;
-    use num_traits::ToPrimitive;
-    use num_traits::{FromPrimitive, One, Pow, Signed, Zero};
+    #[test]
+    fn test_decimal_digits() {
+        let mut digits = Ratio::new(1, 7).decimal_digits();
+        for &d in &[0, 1, 4, 2, 8, 5, 7, 1, 4, 2, 8, 5, 7] {
+            assert_eq!(digits.next(), Some(d));
+        }
+        assert!(Ratio::new(22, 7).decimal_digits().take(4).eq([3, 1, 4, 2]));
+        assert!(Ratio::new(-22, 7)
+            .decimal_digits()
+            .take(4)
+            .eq([-3, 1, 4, 2]));
+        assert!(Ratio::new_raw(1, -8).decimal_digits().eq([0, 1, 2, 5]));
+
+        assert!(_3_2.decimal_digits().eq([1, 5]));
+        assert!(_2.decimal_digits().eq([2]));
+        assert!(_0.decimal_digits().eq([0]));
+        assert!(Ratio::new(1u8, 3).decimal_digits().take(3).eq([0, 3, 3]));
+        assert!(Ratio::new(29u8, 30)
+            .decimal_digits()
+            .take(4)
+            .eq([0, 9, 6, 6]));
+        assert!(Ratio::new_raw(-1i8, -127)
+            .decimal_digits()
+            .take(5)
+            .eq([0, 0, 0, 7, 8]));
+
+        // ten times the remainder doesn't fit
+        let tiny = Ratio::new(1i64, i64::MAX).decimal_digits();
+        assert!(tiny.skip(19).take(5).eq([1, 0, 8, 4, 2]));
+        let near_one = Ratio::new(i64::MAX - 1, i64::MAX).decimal_digits();
+        assert!(near_one.skip(1).take(18).all(|d| d == 9));
+        let near_one = Ratio::new(i64::MAX - 1, i64::MAX).decimal_digits();
+        assert!(near_one.skip(19).take(4).eq([8, 9, 1, 5]));
+        let near_min = Ratio::new(i64::MIN + 1, i64::MIN + 2).decimal_digits();
+        assert!(near_min.take(2).eq([1, 0]));
 
-    pub const _0: Rational64 = Ratio { numer: 0, denom: 1 };
-    pub const _1: Rational64 = Ratio { numer: 1, denom: 1 };
-    pub const _2: Rational64 = Ratio { numer: 2, denom: 1 };
-    pub const _NEG2: Rational64 = Ratio {
-        numer: -2,
-        denom: 1,
-    };
-    pub const _8: Rational64 = Ratio { numer: 8, denom: 1 };
-    pub const _15: Rational64 = Ratio {
-        numer: 15,
-        denom: 1,
-    };
-    pub const _16: Rational64 = Ratio {
-        numer: 16,
-        denom: 1,
-    };
+        #[cfg(feature = "num-bigint")]
+        assert!(to_big(_1_3)
+            .decimal_digits()
+            .take(3)
+            .eq([0, 3, 3].iter().map(|&d| BigInt::from(d))));
+    }
 
-    pub const _1_2: Rational64 = Ratio { numer: 1, denom: 2 };
-    pub const _1_8: Rational64 = Ratio { numer: 1, denom: 8 };
-    pub const _1_15: Rational64 = Ratio {
-        numer: 1,
-        denom: 15,
-    };
-    pub const _1_16: Rational64 = Ratio {
-        numer: 1,
-        denom: 16,
-    };
-    pub const _3_2: Rational64 = Ratio { numer: 3, denom: 2 };
-    pub const _5_2: Rational64 = Ratio { numer: 5, denom: 2 };
-    pub const _NEG1_2: Rational64 = Ratio {
-        numer: -1,
-        denom: 2,
-    };
-    pub const _1_NEG2: Rational64 = Ratio {
-        numer: 1,
-        denom: -2,
-    };
-    pub const _NEG1_NEG2: Rational64 = Ratio {
-        numer: -1,
-        denom: -2,
-    };
-    pub const _1_3: Rational64 = Ratio { numer: 1, denom: 3 };
-    pub const _NEG1_3: Rational64 = Ratio {
-        numer: -1,
-        denom: 3,
-    };
-    pub const _2_3: Rational64 = Ratio { numer: 2, denom: 3 };
-    pub const _NEG2_3: Rational64 = Ratio {
-        numer: -2,
-        denom: 3,
-    };
-    pub const _MIN: Rational64 = Ratio {
-        numer: i64::MIN,
-        denom: 1,
-    };
-    pub const _MIN_P1: Rational64 = Ratio {
-        numer: i64::MIN + 1,
-        denom: 1,
-    };
-    pub const _MAX: Rational64 = Ratio {
-        numer: i64::MAX,
-        denom: 1,
-    };
-    pub const _MAX_M1: Rational64 = Ratio {
-        numer: i64::MAX - 1,
-        denom: 1,
-    };
-    pub const _BILLION: Rational64 = Ratio {
-        numer: 1_000_000_000,
-        denom: 1,
-    };
+    #[test]
+    fn test_diffs() {
+        assert!(Ratio::diffs([_0, _1_2, _1]).eq([_1_2, _1_2]));
+        assert!(Ratio::diffs([_1, _NEG1_2, _1_3, _1_3]).eq([-_3_2, Ratio::new(5, 6), _0]));
+        assert!(Ratio::diffs([_MAX, _MAX_M1]).eq([-_1]));
+        assert_eq!(Ratio::diffs([_1]).count(), 0);
+        assert_eq!(Ratio::diffs(core::iter::empty::<Rational64>()).count(), 0);
 
-    #[cfg(feature = "num-bigint")]
-    pub fn to_big(n: Rational64) -> BigRational {
-        Ratio::new(
-            FromPrimitive::from_i64(n.numer).unwrap(),
-            FromPrimitive::from_i64(n.denom).unwrap(),
-        )
+        let ramp = Ratio::ranged(_0, _2, _1_3, false);
+        assert!(Ratio::diffs(ramp).all(|d| d == _1_3));
     }
-    #[cfg(not(feature = "num-bigint"))]
-    pub fn to_big(n: Rational64) -> Rational64 {
-        Ratio::new(
-            FromPrimitive::from_i64(n.numer).unwrap(),
-            FromPrimitive::from_i64(n.denom).unwrap(),
-        )
+
+    #[test]
+    fn test_ranged() {
+        fn test(
+            start: Rational64,
+            stop: Rational64,
+            step: Rational64,
+            inc: bool,
+            expected: &[Rational64],
+        ) {
+            assert!(Ratio::ranged(start, stop, step, inc).eq(expected.iter().cloned()));
+            assert!(Ratio::ranged(start, stop, step, inc)
+                .rev()
+                .eq(expected.iter().rev().cloned()));
+        }
+
+        let _1_4 = Ratio::new(1, 4);
+        let _3_4 = Ratio::new(3, 4);
+        test(_0, _1, _1_4, false, &[_0, _1_4, _1_2, _3_4]);
+        test(_0, _1, _1_4, true, &[_0, _1_4, _1_2, _3_4, _1]);
+        test(_0, _1, _2_3, true, &[_0, _2_3]);
+        test(_1, _0, -_1_4, true, &[_1, _3_4, _1_2, _1_4, _0]);
+        test(_1, _0, -_1_4, false, &[_1, _3_4, _1_2, _1_4]);
+        test(_1, _NEG1_2, -_1, true, &[_1, _0]);
+
+        test(_1, _1, _1_2, true, &[_1]);
+        test(_1, _1, _1_2, false, &[]);
+        test(_1, _0, _1_2, true, &[]);
+        test(_0, _1, -_1_2, true, &[]);
+
+        // both ends can be consumed until they meet
+        let mut r = Ratio::ranged(_0, _1, _1_4, true);
+        assert_eq!(r.next_back(), Some(_1));
+        assert_eq!(r.next(), Some(_0));
+        assert_eq!(r.next_back(), Some(_3_4));
+        assert_eq!(r.next(), Some(_1_4));
+        assert_eq!(r.next_back(), Some(_1_2));
+        assert_eq!(r.next(), None);
+        assert_eq!(r.next_back(), None);
+
+        // more steps than fit in the integer type
+        let fine = Ratio::ranged(Ratio::from(-63i8), Ratio::from(63), Ratio::new(1, 2), true);
+        assert_eq!(fine.count(), 253);
+        let mut fine = Ratio::ranged(Ratio::from(-63i8), Ratio::from(63), Ratio::new(1, 2), false);
+        assert_eq!(fine.next_back(), Some(Ratio::new(125, 2)));
+        assert_eq!(fine.rev().count(), 251);
+        let fine = Ratio::ranged(Ratio::new(1u8, 3), Ratio::from(85), Ratio::new(2, 3), true);
+        assert!(fine.rev().take(2).eq([Ratio::from(85), Ratio::new(253, 3)]));
+        assert_eq!(
+            Ratio::ranged(Ratio::from(0u8), Ratio::from(255), Ratio::from(1), true)
+                .rev()
+                .count(),
+            256
+        );
+
+        // `stop - start` doesn't fit
+        let (min, max) = (Ratio::from(i64::MIN), Ratio::from(i64::MAX));
+        let mut full = Ratio::ranged(min, max, _1, false);
+        assert_eq!(full.next(), Some(min));
+        assert_eq!(full.next_back(), Some(max - 1));
+        assert_eq!(full.next(), Some(min + 1));
+        let full = Ratio::ranged(max, min, -_1, true);
+        assert!(full.rev().take(3).eq([min, min + 1, min + 2]));
+        let full = Ratio::ranged(
+            Ratio::from(i8::MIN),
+            Ratio::from(i8::MAX),
+            Ratio::from(1),
+            true,
+        );
+        assert!(full.rev().eq((-128..=127).rev().map(Ratio::from)));
+
+        let unsigned = Ratio::ranged(Ratio::new(3u8, 2), Ratio::from(0), Ratio::new(1, 2), true);
+        assert_eq!(unsigned.count(), 0);
+        let unsigned = Ratio::ranged(Ratio::from(0u8), Ratio::new(3, 2), Ratio::new(1, 2), false);
+        assert!(unsigned
+            .rev()
+            .eq([Ratio::from(1), Ratio::new(1, 2), Ratio::from(0)]));
     }
 
     #[test]
-    fn test_test_constants() {
-        // check our constants are what Ratio::new etc. would make.
-        assert_eq!(_0, Zero::zero());
-        assert_eq!(_1, One::one());
-        assert_eq!(_2, Ratio::from_integer(2));
-        assert_eq!(_1_2, Ratio::new(1, 2));
-        assert_eq!(_3_2, Ratio::new(3, 2));
-        assert_eq!(_NEG1_2, Ratio::new(-1, 2));
-        assert_eq!(_2, From::from(2));
+    #[should_panic(expected = "step == 0")]
+    fn test_ranged_zero_step() {
+        let _r = Ratio::ranged(_0, _1, _0, true);
     }
 
     #[test]
-    fn test_new_reduce() {
-        assert_eq!(Ratio::new(2, 2), One::one());
-        assert_eq!(Ratio::new(0, i32::MIN), Zero::zero());
-        assert_eq!(Ratio::new(i32::MIN, i32::MIN), One::one());
+    fn test_from_f64_within() {
+        assert_eq!(Ratio::from_f64_within(0.333333, 100i64, 1e-5), Some(_1_3));
+        assert_eq!(Ratio::from_f64_within(-0.333333, 100i64, 1e-5), Some(-_1_3));
+        assert_eq!(Ratio::from_f64_within(0.3141, 100i64, 1e-5), None);
+        assert_eq!(
+            Ratio::from_f64_within(0.3141, 100i64, 1e-3),
+            Some(Ratio::new(27, 86))
+        );
+        assert_eq!(
+            Ratio::from_f64_within(core::f64::consts::PI, 1000i32, 1e-6),
+            Some(Ratio::new(355, 113))
+        );
+        // the best approximation with a bounded denominator may be a semiconvergent
+        assert_eq!(
+            Ratio::from_f64_within(core::f64::consts::PI, 100i32, 1e-3),
+            Some(Ratio::new(311, 99))
+        );
+        assert_eq!(Ratio::from_f64_within(2.5, 1u8, 0.5), Some(Ratio::from(2)));
+        assert_eq!(
+            Ratio::from_f64_within(2.5, 2u8, 0.0),
+            Some(Ratio::new(5, 2))
+        );
+        assert_eq!(Ratio::from_f64_within(0.0, 7u8, 0.0), Some(Ratio::zero()));
+        // the semiconvergent 261/2 doesn't fit
+        assert_eq!(
+            Ratio::from_f64_within(130.3, 2u8, 1.0),
+            Some(Ratio::from(130))
+        );
+        assert_eq!(Ratio::from_f64_within(130.5, 2u8, 0.4), None);
+        assert_eq!(
+            Ratio::from_f64_within(-100.3, 2i8, 1.0),
+            Some(Ratio::from(-100))
+        );
+        assert_eq!(
+            Ratio::from_f64_within(-50.6, 2i8, 0.2),
+            Some(Ratio::new(-101, 2))
+        );
+
+        assert_eq!(Ratio::from_f64_within(-0.5, 10u8, 0.1), None);
+        assert_eq!(Ratio::from_f64_within(1000.0, 10u8, 0.1), None);
+        assert_eq!(Ratio::from_f64_within(0.5, 0u8, 1.0), None);
+        assert_eq!(Ratio::from_f64_within(f64::NAN, 10i32, 1.0), None);
+        assert_eq!(Ratio::from_f64_within(f64::INFINITY, 10i32, 1.0), None);
     }
+
     #[test]
-    #[should_panic]
-    fn test_new_zero() {
-        let _a = Ratio::new(1, 0);
+    #[cfg(any(feature = "std", feature = "libm"))]
+    fn test_cents() {
+        assert!((_3_2.to_cents() - 701.955).abs() < 1e-3);
+        assert_eq!(_2.to_cents(), 1200.0);
+        assert_eq!(_1.to_cents(), 0.0);
+        assert_eq!(_1_2.to_cents(), -1200.0);
+        assert!(_NEG1_2.to_cents().is_nan());
+
+        assert_eq!(Ratio::from_cents(701.955, 10i64), Some(_3_2));
+        assert_eq!(Ratio::from_cents(-1200.0, 10i64), Some(_1_2));
+        assert_eq!(Ratio::from_cents(386.314, 10u32), Some(Ratio::new(5, 4)));
+        assert_eq!(Ratio::from_cents(_3_2.to_cents(), 1000i64), Some(_3_2));
+        assert_eq!(Ratio::from_cents(f64::NAN, 10i64), None);
+        assert_eq!(Ratio::from_cents(100.0, 0i64), None);
     }
 
     #[test]
@@ -1893,7 +5321,110 @@ mod test {
     }
 
     #[test]
-    #[allow(clippy::eq_op)]
+    fn test_approximate_pi_e() {
+        use core::f64::consts::{E, PI};
+
+        assert_eq!(Ratio::approximate_pi(1), Ratio::from(3));
+        assert_eq!(Ratio::approximate_pi(7), Ratio::new(22, 7));
+        assert_eq!(Ratio::approximate_pi(56), Ratio::new(22, 7));
+        assert_eq!(Ratio::approximate_pi(57), Ratio::new(179, 57));
+        assert_eq!(Ratio::approximate_pi(100), Ratio::new(311, 99));
+        assert_eq!(Ratio::approximate_pi(113u32), Ratio::new(355, 113));
+        assert_eq!(Ratio::approximate_e(1), Ratio::from(3));
+        assert_eq!(Ratio::approximate_e(7), Ratio::new(19, 7));
+        assert_eq!(Ratio::approximate_e(18), Ratio::new(49, 18));
+        assert_eq!(Ratio::approximate_e(100u8), Ratio::new(193, 71));
+
+        // the numerator of the closest ratio doesn't fit
+        assert_eq!(Ratio::approximate_pi(255u8), Ratio::new(22, 7));
+        assert_eq!(Ratio::approximate_pi(i8::MAX), Ratio::new(22, 7));
+        assert_eq!(
+            Ratio::approximate_pi(u64::MAX),
+            Ratio::new(2646693125139304345, 842468587426513207)
+        );
+        assert_eq!(Ratio::approximate_e(255u8), Ratio::new(193, 71));
+        assert_eq!(Ratio::approximate_e(i8::MAX), Ratio::new(106, 39));
+
+        // Check against every denominator for small bounds.
+        fn brute_force(x: f64, max_denom: i64) -> Rational64 {
+            (1..=max_denom)
+                .map(|d| Ratio::new((x * d as f64).round() as i64, d))
+                .min_by(|a, b| {
+                    let dist = |r: &Rational64| (r.to_f64().unwrap() - x).abs();
+                    dist(a).partial_cmp(&dist(b)).unwrap()
+                })
+                .unwrap()
+        }
+        for max_denom in 1..300 {
+            assert_eq!(Ratio::approximate_pi(max_denom), brute_force(PI, max_denom));
+            assert_eq!(Ratio::approximate_e(max_denom), brute_force(E, max_denom));
+        }
+
+        let pi = Ratio::approximate_pi(i64::MAX / 4);
+        assert_eq!(pi.to_f64(), Some(PI));
+        let e = Ratio::approximate_e(u64::MAX / 3);
+        assert_eq!(e.to_f64(), Some(E));
+    }
+
+    #[test]
+    fn test_approximate_float_i128() {
+        let two_127 = 2f64.powi(127);
+        assert_eq!(Ratio::<i128>::from_f64(two_127), None);
+        assert_eq!(Ratio::<i128>::from_f64(-two_127), None);
+        assert_eq!(Ratio::<i128>::from_f32(two_127 as f32), None);
+        let below = two_127 - 2f64.powi(74);
+        assert_eq!(
+            Ratio::<i128>::from_f64(below),
+            Some(Ratio::from_integer(below as i128))
+        );
+        assert_eq!(
+            Ratio::<u128>::from_f64(two_127),
+            Some(Ratio::from_integer(1 << 127))
+        );
+        assert_eq!(
+            Ratio::<u128>::from_f32(f32::MAX),
+            Some(Ratio::from_integer(f32::MAX as u128))
+        );
+        assert_eq!(Ratio::<u128>::from_f32(f32::INFINITY), None);
+
+        // Deep expansions stop before the convergents overflow.
+        for &x in &[1e-30, 1.0 / 3e20, core::f64::consts::PI, 1e30 + 0.5, -7e-39] {
+            let (r, _) = Ratio::<i128>::approximate_float_with_error(x, 0.0, 1000).unwrap();
+            assert!(r.denom() > &0);
+        }
+        let (r, _) =
+            Ratio::<u128>::approximate_float_unsigned_with_error(1e-20f32, 0.0, 1000).unwrap();
+        assert!(r.denom() > &0);
+    }
+
+    #[test]
+    fn test_approximate_float_with_error() {
+        let (r, error) = Ratio::<i64>::approximate_float_with_error(0.5f64, 1e-10, 30).unwrap();
+        assert_eq!(r, _1_2);
+        assert_eq!(error, 0.0);
+
+        let pi = core::f64::consts::PI;
+        let (r, error) = Ratio::<i64>::approximate_float_with_error(pi, 1e-6, 30).unwrap();
+        assert!(error <= 1e-6);
+        assert_eq!(error, (r.to_f64().unwrap() - pi).abs());
+        assert_eq!(r, Ratio::new(355, 113));
+
+        let (r, error) = Ratio::<i32>::approximate_float_with_error(-pi, 1e-2, 30).unwrap();
+        assert!(error <= 1e-2);
+        assert_eq!(r, Ratio::new(-22, 7));
+
+        let (r, error) = Ratio::<u32>::approximate_float_unsigned_with_error(pi, 1e-2, 30).unwrap();
+        assert!(error <= 1e-2);
+        assert_eq!(r, Ratio::new(22, 7));
+
+        assert_eq!(
+            Ratio::<i32>::approximate_float_with_error(f64::NAN, 1e-2, 30),
+            None
+        );
+    }
+
+    #[test]
+    #[allow(clippy::eq_op, clippy::nonminimal_bool)]
     fn test_cmp() {
         assert!(_0 == _0 && _1 == _1);
         assert!(_0 != _1 && _1 != _0);
@@ -1910,6 +5441,69 @@ mod test {
         assert_eq!(_0, _0_2);
     }
 
+    #[test]
+    fn test_cmp_signs() {
+        // strictly ascending, mixing signs and raw negative denominators
+        let sorted = [
+            _MIN,
+            _MIN_P1,
+            _NEG2,
+            Ratio::new_raw(3, -2),
+            _NEG1_2,
+            Ratio::new_raw(1, -3),
+            _0,
+            Ratio::new_raw(-1, -3),
+            _1_2,
+            Ratio::new_raw(-3, -2),
+            _2,
+            _MAX_M1,
+            _MAX,
+        ];
+        for (i, a) in sorted.iter().enumerate() {
+            for (j, b) in sorted.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{:?} vs {:?}", a, b);
+            }
+        }
+
+        assert_eq!(Ratio::new_raw(0, -3).cmp(&_0), core::cmp::Ordering::Equal);
+        assert!(Ratio::new(1u8, 2) > Ratio::new(0, 1));
+    }
+
+    #[test]
+    #[cfg(feature = "num-bigint")]
+    fn test_cmp_cross() {
+        use core::cmp::Ordering;
+
+        // simple xorshift, to compare against the generic path on arbitrary values
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 16) as i64 - (1 << 47)
+        };
+        for _ in 0..1000 {
+            let mut parts = [next(), next(), next(), next()];
+            if parts[1] == 0 {
+                parts[1] = 1;
+            }
+            if parts[3] == 0 {
+                parts[3] = -1;
+            }
+            let a = BigRational::new_raw(parts[0].into(), parts[1].into());
+            let b = BigRational::new_raw(parts[2].into(), parts[3].into());
+            assert_eq!(a.cmp_cross(&b), a.cmp(&b));
+            assert_eq!(b.cmp_cross(&a), b.cmp(&a));
+            assert_eq!(a.cmp_cross(&a), Ordering::Equal);
+        }
+
+        let a = to_big(_1_2);
+        let b = BigRational::new_raw((-2).into(), (-4).into());
+        assert_eq!(a.cmp_cross(&b), Ordering::Equal);
+        assert_eq!(to_big(_NEG1_2).cmp_cross(&to_big(_0)), Ordering::Less);
+        assert_eq!(to_big(_MAX).cmp_cross(&to_big(_MAX_M1)), Ordering::Greater);
+    }
+
     #[test]
     fn test_cmp_overflow() {
         use core::cmp::Ordering;
@@ -1949,6 +5543,35 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_from_scientific_parts() {
+        fn test(mantissa: i64, exp10: i32, expected: Rational64) {
+            assert_eq!(Ratio::from_scientific_parts(mantissa, exp10), expected);
+            assert_eq!(
+                Ratio::checked_from_scientific_parts(mantissa, exp10),
+                Some(expected)
+            );
+        }
+        test(15, -1, _3_2);
+        test(-15, -1, -_3_2);
+        test(15, 0, _15);
+        test(1, 9, _BILLION);
+        test(0, -100, _0);
+        test(0, 100, _0);
+        test(100_000_000_000_000_000, -19, Ratio::new(1, 100));
+        test(1, -18, Ratio::new(1, 1_000_000_000_000_000_000));
+
+        assert_eq!(Rational64::checked_from_scientific_parts(1, -19), None);
+        assert_eq!(Rational64::checked_from_scientific_parts(1, 19), None);
+        assert_eq!(Rational64::checked_from_scientific_parts(10, 18), None);
+        assert_eq!(Rational64::checked_from_scientific_parts(3, i32::MIN), None);
+        assert_eq!(
+            Ratio::<u8>::checked_from_scientific_parts(25, 1),
+            Some(Ratio::from_integer(250))
+        );
+        assert_eq!(Ratio::<u8>::checked_from_scientific_parts(26, 1), None);
+    }
+
     #[test]
     fn test_to_integer() {
         assert_eq!(_0.to_integer(), 0);
@@ -1979,13 +5602,123 @@ mod test {
     }
 
     #[test]
-    fn test_is_integer() {
-        assert!(_0.is_integer());
-        assert!(_1.is_integer());
-        assert!(_2.is_integer());
-        assert!(!_1_2.is_integer());
-        assert!(!_3_2.is_integer());
-        assert!(!_NEG1_2.is_integer());
+    fn test_is_integer() {
+        assert!(_0.is_integer());
+        assert!(_1.is_integer());
+        assert!(_2.is_integer());
+        assert!(!_1_2.is_integer());
+        assert!(!_3_2.is_integer());
+        assert!(!_NEG1_2.is_integer());
+    }
+
+    #[test]
+    fn test_to_dyadic() {
+        assert_eq!(_1_3.to_dyadic(4), Ratio::new(5, 16));
+        assert_eq!(Ratio::new(-1, 3).to_dyadic(4), Ratio::new(-5, 16));
+        assert_eq!(Ratio::new(2, 3).to_dyadic(4), Ratio::new(11, 16));
+        assert_eq!(Ratio::new(3, 8).to_dyadic(3), Ratio::new(3, 8));
+        assert_eq!(_1_3.to_dyadic(0), _0);
+        assert_eq!(Ratio::new(5, 3).to_dyadic(0), _2);
+
+        // ties go to the even multiple
+        assert_eq!(Ratio::new(3, 8).to_dyadic(2), _1_2);
+        assert_eq!(Ratio::new(5, 8).to_dyadic(2), _1_2);
+        assert_eq!(Ratio::new(-3, 8).to_dyadic(2), _NEG1_2);
+        assert_eq!(_3_2.to_dyadic(0), _2);
+        assert_eq!(_1_2.to_dyadic(0), _0);
+        assert_eq!(Ratio::<i64>::new_raw(5, -8).to_dyadic(2), _NEG1_2);
+
+        assert!(Ratio::new(7, 10).to_dyadic(8).is_dyadic());
+        assert_eq!(Ratio::new(100u8, 3).to_dyadic(1), Ratio::new(67, 2));
+    }
+
+    #[test]
+    fn test_q_format() {
+        // round trip of a dyadic value
+        let r = Ratio::new(-13, 16);
+        assert_eq!(r.to_q_format(4), Some(-13));
+        assert_eq!(r.to_q_format(6), Some(-52));
+        assert_eq!(Ratio::from_q_format(-13, 4), r);
+        assert_eq!(Ratio::from_q_format(-52, 6), r);
+        assert_eq!(Ratio::<i64>::from_q_format(0, 100), _0);
+
+        // Q0.7 in i8, where 2^7 itself doesn't fit
+        assert_eq!(Ratio::new(1i8, 3).to_q_format(7), Some(43));
+        assert_eq!(Ratio::from(-1i8).to_q_format(7), Some(-128));
+        assert_eq!(Ratio::from(1i8).to_q_format(7), None);
+        assert_eq!(Ratio::from_q_format(-128i8, 7), Ratio::from(-1));
+        assert_eq!(Ratio::from_q_format(64i8, 7), Ratio::new(1, 2));
+        assert_eq!(Ratio::new(100u8, 3).to_q_format(2), Some(133));
+        assert_eq!(Ratio::new(100u8, 3).to_q_format(3), None);
+        assert_eq!(Ratio::<i8>::zero().to_q_format(u32::MAX), Some(0));
+
+        // ties go to even, like to_dyadic
+        for numer in -40..=40 {
+            for denom in 1..=20 {
+                let r = Ratio::new(numer, denom);
+                for bits in 0..8 {
+                    let expected = r.to_dyadic(bits) * (1 << bits);
+                    assert_eq!(r.to_q_format(bits), Some(expected.to_integer()));
+                }
+            }
+        }
+
+        for value in i8::MIN..=i8::MAX {
+            for bits in 0..7 {
+                let r = Ratio::from_q_format(value, bits);
+                assert_eq!(r.to_q_format(bits), Some(value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_dyadic() {
+        assert!(Ratio::new(3, 8).is_dyadic());
+        assert!(!Ratio::new(1, 3).is_dyadic());
+        assert!(_0.is_dyadic());
+        assert!(_2.is_dyadic());
+        assert!(_NEG1_2.is_dyadic());
+        assert!(!Ratio::new(3, 10).is_dyadic());
+        assert!(Ratio::<i64>::new_raw(6, 24).is_dyadic());
+        assert!(!Ratio::<i64>::new_raw(6, 36).is_dyadic());
+        assert!(Ratio::<i64>::new_raw(3, -8).is_dyadic());
+        assert!(Ratio::<i64>::new_raw(1, i64::MIN).is_dyadic());
+        assert!(Ratio::<i64>::new_raw(i64::MIN, i64::MIN).is_dyadic());
+        assert!(!Ratio::<i64>::new_raw(i64::MIN, 3 << 20).is_dyadic());
+        assert!(Ratio::<i64>::new_raw(3 << 20, i64::MIN).is_dyadic());
+        assert!(!Ratio::<i64>::new_raw(1, 0).is_dyadic());
+        assert!(Ratio::new(1u8, 128).is_dyadic());
+    }
+
+    #[test]
+    fn test_terminates_in_base() {
+        assert!(Ratio::new(1, 8).terminates_in_base(10));
+        assert!(!_1_3.terminates_in_base(10));
+        assert!(_1_3.terminates_in_base(3));
+        assert!(Ratio::new(7, 360).terminates_in_base(30));
+        assert!(!Ratio::new(7, 360).terminates_in_base(6));
+        assert!(Ratio::new(1i64, 1 << 40).terminates_in_base(2));
+        assert!(_2.terminates_in_base(7));
+        assert!(_0.terminates_in_base(7));
+        assert!(Ratio::<i64>::new_raw(3, 9).terminates_in_base(3));
+        assert!(!Ratio::<i64>::new_raw(3, 9).terminates_in_base(2));
+        assert!(Ratio::<i64>::new_raw(1, -12).terminates_in_base(6));
+        assert!(Ratio::<i64>::new_raw(1, i64::MIN).terminates_in_base(10));
+        assert!(Ratio::<i64>::new_raw(i64::MIN, i64::MIN).terminates_in_base(10));
+        assert!(!Ratio::<i64>::new_raw(i64::MIN, 0).terminates_in_base(10));
+        assert!(!Ratio::<i64>::new_raw(i64::MIN, 3).terminates_in_base(10));
+        assert!(!Ratio::<i64>::new_raw(1, 0).terminates_in_base(10));
+        assert!(Ratio::new(1u8, 250).terminates_in_base(10));
+        for denom in 1..=100 {
+            let r = Ratio::new(1, denom);
+            assert_eq!(r.terminates_in_base(2), r.is_dyadic());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "base < 2")]
+    fn test_terminates_in_base_1() {
+        let _ = _1_3.terminates_in_base(1);
     }
 
     #[cfg(not(feature = "std"))]
@@ -2058,6 +5791,30 @@ mod test {
         };
     }
 
+    #[test]
+    fn test_show_zero() {
+        for zero in &[
+            _0,
+            Ratio::new_raw(0, -5),
+            Ratio::new_raw(0, 3),
+            Ratio::new(0, -1),
+        ] {
+            assert_eq!(*zero, _0);
+            assert_fmt_eq!(format_args!("{}", zero), "0");
+            assert_fmt_eq!(format_args!("{:+}", zero), "+0");
+            assert_fmt_eq!(format_args!("{:#x}", zero), "0x0");
+            assert_fmt_eq!(format_args!("{:03}", zero), "000");
+        }
+        assert_fmt_eq!(format_args!("{}", Ratio::new_raw(0u8, 7)), "0");
+
+        #[cfg(feature = "std")]
+        {
+            let mut buf = std::string::String::new();
+            Ratio::new_raw(0, -5).write_to(&mut buf).unwrap();
+            assert_eq!(buf, "0");
+        }
+    }
+
     #[test]
     fn test_show() {
         // Test:
@@ -2151,7 +5908,10 @@ mod test {
         use super::{to_big, _0, _1, _1_2, _2, _3_2, _5_2, _MAX, _MAX_M1, _MIN, _MIN_P1, _NEG1_2};
         use core::fmt::Debug;
         use num_integer::Integer;
-        use num_traits::{Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, NumAssign};
+        use num_traits::{
+            Bounded, CheckedAdd, CheckedDiv, CheckedEuclid, CheckedMul, CheckedSub, Euclid,
+            NumAssign, Zero,
+        };
 
         #[test]
         fn test_add() {
@@ -2289,6 +6049,26 @@ mod test {
             test_sub_typed_overflow::<i128>();
         }
 
+        #[test]
+        fn test_add_sub_common_factor() {
+            // the lcm of the denominators overflows, but the results fit
+            let a = Ratio::new(1i64, 3 << 60);
+            let b = Ratio::new(1i64, 5 << 60);
+            assert_eq!(a + b, Ratio::new(1, 15 << 57));
+            assert_eq!(a - b, Ratio::new(1, 15 << 59));
+            let mut x = a;
+            x += b;
+            assert_eq!(x, Ratio::new(1, 15 << 57));
+            let mut x = a;
+            x -= b;
+            assert_eq!(x, Ratio::new(1, 15 << 59));
+
+            let a = Ratio::new(1i64, 3_000_000_000);
+            let b = Ratio::new(1i64, 6_000_000_000);
+            assert_eq!(a + b, Ratio::new(1, 2_000_000_000));
+            assert_eq!(a - b, Ratio::new(1, 6_000_000_000));
+        }
+
         #[test]
         fn test_mul() {
             fn test(a: Rational64, b: Rational64, c: Rational64) {
@@ -2323,6 +6103,42 @@ mod test {
             test_assign(_1_2, 2, _1);
         }
 
+        #[test]
+        fn test_checked_mul_cross_cancel() {
+            // 200*3 overflows u8, but the factors cancel before multiplying
+            let a = Ratio::new(200u8, 3);
+            let b = Ratio::new(3u8, 200);
+            assert_eq!(a.checked_mul(&b), Some(Ratio::from(1)));
+            assert_eq!(a * b, Ratio::from(1));
+
+            let a = Ratio::new(100u8, 3);
+            let b = Ratio::new(6u8, 25);
+            assert_eq!(a.checked_mul(&b), Some(Ratio::from(8)));
+            assert_eq!(a.checked_mul(&Ratio::new(9u8, 7)), None);
+        }
+
+        #[test]
+        fn test_mul_div_cross_cancel() {
+            // every product of the parts overflows i32, but they all cancel
+            let a = Ratio::new(i32::MAX, i32::MAX - 1);
+            let b = Ratio::new(i32::MAX - 1, i32::MAX);
+            assert_eq!(a * b, Ratio::from(1));
+            assert_eq!(a / a, Ratio::from(1));
+            assert_eq!(a / b.recip(), Ratio::from(1));
+            let mut x = a;
+            x *= b;
+            assert_eq!(x, Ratio::from(1));
+            let mut x = a;
+            x /= a;
+            assert_eq!(x, Ratio::from(1));
+
+            // partial cancellation
+            let c = Ratio::new(i32::MAX, 2);
+            let d = Ratio::new(4, i32::MAX);
+            assert_eq!(c * d, Ratio::from(2));
+            assert_eq!(c / d.recip(), Ratio::from(2));
+        }
+
         #[test]
         fn test_mul_overflow() {
             fn test_mul_typed_overflow<T>()
@@ -2547,6 +6363,20 @@ mod test {
             test(_1_2, _NEG1_2);
             test(-_1, _1);
         }
+        #[test]
+        fn test_neg_reduced() {
+            let a = Rational64::new_raw(2, 4);
+            let neg = -a;
+            assert_eq!((neg.numer, neg.denom), (-2, 4));
+            let neg = a.neg_reduced();
+            assert_eq!((neg.numer, neg.denom), (-1, 2));
+            assert_eq!(neg, -a);
+
+            let neg = Rational64::new_raw(3, -6).neg_reduced();
+            assert_eq!((neg.numer, neg.denom), (1, 2));
+            assert_eq!(_0.neg_reduced(), _0);
+        }
+
         #[test]
         #[allow(clippy::eq_op)]
         fn test_zero() {
@@ -2557,11 +6387,182 @@ mod test {
             assert_eq!(_0 - _0, _0);
         }
         #[test]
-        #[should_panic]
+        #[should_panic(expected = "division by zero rational")]
         fn test_div_0() {
             let _a = _1 / _0;
         }
 
+        #[test]
+        #[should_panic(expected = "division by zero rational")]
+        fn test_div_int_0() {
+            let _a = _0 / 0;
+        }
+
+        #[test]
+        fn test_checked_div_by_int() {
+            assert_eq!(_1_2.checked_div_by_int(&0), None);
+            assert_eq!(_0.checked_div_by_int(&0), None);
+            assert_eq!(_3_2.checked_div_by_int(&3), Some(_1_2));
+            assert_eq!(_1_2.checked_div_by_int(&-2), Some(Ratio::new(-1, 4)));
+            assert_eq!(_MIN.checked_div_by_int(&-1), None);
+            assert_eq!(_MIN.checked_div_by_int(&2), Some(Ratio::from(i64::MIN / 2)));
+            assert_eq!(Ratio::new(1, i64::MAX).checked_div_by_int(&2), None);
+            for &(r, i) in &[(_1_2, 5), (_NEG1_2, 3), (_MAX, 7)] {
+                assert_eq!(r.checked_div_by_int(&i), Some(r / i));
+            }
+        }
+
+        #[test]
+        fn test_overflowing_add() {
+            let r = |n: i8, d: i8| Ratio::new(n, d);
+            assert_eq!(r(1, 2).overflowing_add(&r(1, 3)), (r(5, 6), false));
+            assert_eq!(r(126, 1).overflowing_add(&r(1, 1)), (r(127, 1), false));
+            assert_eq!(r(-127, 1).overflowing_add(&r(-1, 1)), (r(-128, 1), false));
+
+            let (sum, overflow) = r(127, 1).overflowing_add(&r(1, 1));
+            assert!(overflow);
+            assert_eq!(sum.into_raw(), (-128, 1));
+            let (sum, overflow) = r(-128, 1).overflowing_add(&r(-1, 1));
+            assert!(overflow);
+            assert_eq!(sum.into_raw(), (127, 1));
+            let (sum, overflow) = r(100, 3).overflowing_add(&r(100, 3));
+            assert!(overflow);
+            assert_eq!(sum.into_raw(), (-56, 3));
+
+            let (sum, overflow) = Ratio::new(255u8, 2).overflowing_add(&Ratio::new(1, 3));
+            assert!(overflow);
+            assert_eq!(sum.into_raw(), (255u8.wrapping_mul(3).wrapping_add(2), 6));
+
+            // the lcm itself wraps
+            let (sum, overflow) = r(1, 9).overflowing_add(&r(1, 25));
+            assert!(overflow);
+            assert_eq!(sum.into_raw(), (34, 225u8 as i8));
+            let (sum, overflow) = r(1, 12).overflowing_add(&r(1, 22));
+            assert!(overflow);
+            assert_eq!(sum.into_raw(), (17, 132u8 as i8));
+            let (sum, overflow) = Ratio::new(1u8, 17).overflowing_add(&Ratio::new(1, 16));
+            assert!(overflow);
+            assert_eq!(sum.into_raw(), (33, 16));
+        }
+
+        #[test]
+        fn test_checked_add_lcm() {
+            // lcm(b, d) overflows, but the sum fits
+            let a = Ratio::new(1i64, 3 << 60);
+            let b = Ratio::new(1i64, 5 << 60);
+            assert_eq!(a.checked_add(&b), Some(Ratio::new(1, 15 << 57)));
+            assert_eq!(a.checked_sub(&b), Some(Ratio::new(1, 15 << 59)));
+            assert_eq!(a.checked_add_lcm(&b), Some(Ratio::new(1, 15 << 57)));
+            assert_eq!(a.checked_add_lcm(&-b), Some(Ratio::new(1, 15 << 59)));
+
+            assert_eq!(_MAX.checked_add_lcm(&_1), None);
+            assert_eq!(_MIN.checked_add_lcm(&_NEG1_2), None);
+            assert_eq!(
+                Ratio::new(1i64, 3 << 60).checked_add_lcm(&Ratio::new(1, 7)),
+                None
+            );
+            assert_eq!(
+                Ratio::new(1u8, 2).checked_add_lcm(&Ratio::new(1, 2)),
+                Some(Ratio::from(1))
+            );
+            for &(x, y) in &[(_1_2, _3_2), (_NEG1_2, _5_2), (_2, _NEG1_2), (_0, _5_2)] {
+                assert_eq!(x.checked_add_lcm(&y), Some(x + y));
+            }
+        }
+
+        #[test]
+        fn test_checked_sub_int() {
+            assert_eq!(_3_2.checked_sub_int(&1), Some(_1_2));
+            assert_eq!(_1_2.checked_sub_int(&-1), Some(_3_2));
+            assert_eq!(_0.checked_sub_int(&0), Some(_0));
+            assert_eq!(_MIN_P1.checked_sub_int(&1), Some(_MIN));
+            assert_eq!(_MIN.checked_sub_int(&1), None);
+            assert_eq!(_MAX_M1.checked_sub_int(&-1), Some(_MAX));
+            assert_eq!(_MAX.checked_sub_int(&-1), None);
+            assert_eq!(_0.checked_sub_int(&i64::MIN), None);
+            assert_eq!(Ratio::new(1, 3).checked_sub_int(&(i64::MAX / 2)), None);
+            assert_eq!(Ratio::new(1u8, 2).checked_sub_int(&1), None);
+            for &(r, i) in &[(_1_2, 5), (_NEG1_2, -3), (_MAX, 7)] {
+                assert_eq!(r.checked_sub_int(&i), Some(r - i));
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "division by zero rational")]
+        fn test_div_assign_0() {
+            let mut a = _1_2;
+            a /= _0;
+        }
+
+        #[test]
+        #[should_panic(expected = "division by zero rational")]
+        fn test_rem_0() {
+            let _a = _1_2 % _0;
+        }
+
+        #[test]
+        #[should_panic(expected = "division by zero rational")]
+        fn test_rem_int_0() {
+            let _a = _1_2 % 0;
+        }
+
+        #[test]
+        #[should_panic(expected = "division by zero rational")]
+        fn test_rem_assign_0() {
+            let mut a = _1_2;
+            a %= 0;
+        }
+
+        #[test]
+        fn test_euclid() {
+            fn test(a: Rational64, b: Rational64, q: Rational64, r: Rational64) {
+                assert_eq!(a.div_euclid(&b), q);
+                assert_eq!(a.rem_euclid(&b), r);
+                assert_eq!(a.checked_div_euclid(&b), Some(q));
+                assert_eq!(a.checked_rem_euclid(&b), Some(r));
+                assert_eq!(q * b + r, a);
+            }
+
+            test(Ratio::new(7, 2), _1, Ratio::from(3), _1_2);
+            test(Ratio::new(-7, 2), _1, Ratio::from(-4), _1_2);
+            test(Ratio::new(7, 2), -_1, Ratio::from(-3), _1_2);
+            test(Ratio::new(-7, 2), -_1, Ratio::from(4), _1_2);
+            test(_5_2, _3_2, _1, _1);
+            test(-_5_2, _3_2, -_2, _1_2);
+            test(_2, _NEG1_2, Ratio::from(-4), _0);
+            test(_1_2, _2, _0, _1_2);
+        }
+
+        #[test]
+        fn test_checked_euclid_overflow() {
+            let min = Ratio::from_integer(i32::MIN);
+            let max = Ratio::from_integer(i32::MAX);
+            let neg1 = Ratio::from_integer(-1);
+            let half = Ratio::new(1, 2);
+
+            assert_eq!(min.checked_div_euclid(&neg1), None);
+            assert_eq!(min.checked_rem_euclid(&neg1), None);
+            assert_eq!(max.checked_div_euclid(&half), None);
+            assert_eq!(max.checked_rem_euclid(&half), None);
+            assert_eq!(max.checked_div_euclid(&Ratio::zero()), None);
+            assert_eq!(max.checked_rem_euclid(&Ratio::zero()), None);
+
+            assert_eq!(min.checked_div_euclid(&max), Some(Ratio::from_integer(-2)));
+            assert_eq!(
+                min.checked_rem_euclid(&max),
+                Some(Ratio::from_integer(i32::MAX - 1))
+            );
+            // the exact quotient -MAX/2^31 isn't representable
+            assert_eq!(max.checked_div_euclid(&min), None);
+            let min_p1 = Ratio::from_integer(i32::MIN + 1);
+            assert_eq!(max.checked_div_euclid(&min_p1), Some(neg1));
+            assert_eq!(max.checked_rem_euclid(&min_p1), Some(Ratio::zero()));
+            assert_eq!(
+                min.checked_rem_euclid(&Ratio::from_integer(2)),
+                Some(Ratio::zero())
+            );
+        }
+
         #[test]
         fn test_checked_failures() {
             let big = Ratio::new(128u8, 1);
@@ -2713,57 +6714,539 @@ mod test {
     }
 
     #[test]
-    fn test_recip() {
-        assert_eq!(_1 * _1.recip(), _1);
-        assert_eq!(_2 * _2.recip(), _1);
-        assert_eq!(_1_2 * _1_2.recip(), _1);
-        assert_eq!(_3_2 * _3_2.recip(), _1);
-        assert_eq!(_NEG1_2 * _NEG1_2.recip(), _1);
+    fn test_as_mixed() {
+        assert_eq!(Ratio::new(7, 3).as_mixed(), (2, _1_3));
+        assert_eq!(Ratio::new(-7, 3).as_mixed(), (-2, _1_3));
+        assert_eq!(Rational64::new_raw(7, -3).as_mixed(), (-2, _1_3));
+        assert_eq!(Rational64::new_raw(-14, -6).as_mixed(), (2, _1_3));
+        assert_eq!(_2.as_mixed(), (2, _0));
+        assert_eq!(_NEG2.as_mixed(), (-2, _0));
+        assert_eq!(_0.as_mixed(), (0, _0));
+        assert_eq!(_1_3.as_mixed(), (0, _1_3));
+        assert_eq!(_NEG1_3.as_mixed(), (0, _NEG1_3));
+        assert_eq!(Ratio::new(7u8, 3).as_mixed(), (2, Ratio::new(1, 3)));
+    }
+
+    #[test]
+    fn test_recip() {
+        assert_eq!(_1 * _1.recip(), _1);
+        assert_eq!(_2 * _2.recip(), _1);
+        assert_eq!(_1_2 * _1_2.recip(), _1);
+        assert_eq!(_3_2 * _3_2.recip(), _1);
+        assert_eq!(_NEG1_2 * _NEG1_2.recip(), _1);
+
+        assert_eq!(_3_2.recip(), _2_3);
+        assert_eq!(_NEG1_2.recip(), _NEG2);
+        assert_eq!(_NEG1_2.recip().denom(), &1);
+    }
+
+    #[test]
+    fn test_ilog() {
+        assert_eq!(_8.ilog(&_2), Some(3));
+        assert_eq!(_1_8.ilog(&_2), Some(-3));
+        assert_eq!(Ratio::new(9, 1).ilog(&_2), Some(3));
+        assert_eq!(Ratio::new(1, 9).ilog(&_2), Some(-4));
+        assert_eq!(_1.ilog(&_2), Some(0));
+        assert_eq!(_3_2.ilog(&_3_2), Some(1));
+        assert_eq!(_16.ilog(&_3_2), Some(6));
+        assert_eq!(_MAX.ilog(&_2), Some(62));
+        assert_eq!(_MAX.recip().ilog(&_2), Some(-63));
+
+        assert_eq!(_0.ilog(&_2), None);
+        assert_eq!(_NEG2.ilog(&_2), None);
+        assert_eq!(_8.ilog(&_1), None);
+        assert_eq!(_8.ilog(&_1_2), None);
+    }
+
+    #[test]
+    fn test_strict_eq() {
+        let raw = Rational64::new_raw(2, 4);
+        assert_eq!(raw, _1_2);
+        assert!(!raw.strict_eq(&_1_2));
+        assert!(!_1_2.strict_eq(&raw));
+        assert!(!raw.strict_eq(&raw));
+        assert!(_1_2.strict_eq(&Ratio::new(2, 4)));
+        assert!(!_1_NEG2.strict_eq(&_NEG1_2));
+        assert!(_NEG1_2.strict_eq(&Ratio::new(1, -2)));
+        assert!(_0.strict_eq(&_0));
+        assert!(!Rational64::new_raw(0, 2).strict_eq(&_0));
+        assert!(!_1_2.strict_eq(&_3_2));
+    }
+
+    #[test]
+    fn test_is_close() {
+        let tol = Ratio::new(1, 1000);
+        assert!(_1_3.is_close(&Ratio::new(333, 1000), &tol));
+        assert!(_1_3.is_close(&Ratio::new(1001, 3000), &tol));
+        assert!(_1_3.is_close(&_1_3, &_0));
+        assert!(!_1_3.is_close(&_1_2, &tol));
+        assert!(!_NEG1_3.is_close(&_1_3, &tol));
+
+        let tol = Ratio::new(1u8, 10);
+        assert!(Ratio::new(1u8, 3).is_close(&Ratio::new(3u8, 10), &tol));
+        assert!(!Ratio::new(1u8, 3).is_close(&Ratio::new(1u8, 2), &tol));
+
+        // MAX - MIN doesn't fit
+        assert!(!_MAX.is_close(&_MIN, &_1));
+        assert!(!_MIN.is_close(&_MAX, &_MAX));
+        assert!(_NEG1_2.is_close(&_1_2, &_1));
+        assert!(!_NEG1_2.is_close(&_1_2, &Ratio::new(999, 1000)));
+        assert!(Ratio::from(i64::MIN + 2).is_close(&_1, &_MAX));
+        assert!(!_MIN_P1.is_close(&_1, &_MAX));
+        assert!(!_MIN.is_close(&_1, &Ratio::new(-1, 2)));
+    }
+
+    #[test]
+    fn test_interpolate() {
+        let points = [(_0, _1), (_1, _2), (_2, _1_2)];
+        assert_eq!(Ratio::interpolate(&points, &_1_2), Some(_3_2));
+        assert_eq!(Ratio::interpolate(&points, &_0), Some(_1));
+        assert_eq!(Ratio::interpolate(&points, &_1), Some(_2));
+        assert_eq!(Ratio::interpolate(&points, &_2), Some(_1_2));
+        assert_eq!(Ratio::interpolate(&points, &_3_2), Some(Ratio::new(5, 4)));
+        assert_eq!(Ratio::interpolate(&points, &_NEG1_2), None);
+        assert_eq!(Ratio::interpolate(&points, &_5_2), None);
+
+        assert_eq!(Ratio::interpolate(&[], &_0), None);
+        assert_eq!(Ratio::interpolate(&[(_1, _2)], &_1), Some(_2));
+        assert_eq!(Ratio::interpolate(&[(_1, _2)], &_0), None);
+        assert_eq!(Ratio::interpolate(&[(_1, _2), (_0, _1)], &_1_2), None);
+        assert_eq!(Ratio::interpolate(&[(_1, _2), (_1, _1)], &_1), None);
+
+        let points = [
+            (Ratio::new(0u8, 1), Ratio::new(4u8, 1)),
+            (Ratio::new(4u8, 1), Ratio::new(0u8, 1)),
+        ];
+        assert_eq!(
+            Ratio::interpolate(&points, &Ratio::new(1u8, 1)),
+            Some(Ratio::new(3u8, 1))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn test_recip_fail() {
+        let _a = Ratio::new(0, 1).recip();
+    }
+
+    #[test]
+    fn test_pow() {
+        fn test(r: Rational64, e: i32, expected: Rational64) {
+            assert_eq!(r.pow(e), expected);
+            assert_eq!(Pow::pow(r, e), expected);
+            assert_eq!(Pow::pow(r, &e), expected);
+            assert_eq!(Pow::pow(&r, e), expected);
+            assert_eq!(Pow::pow(&r, &e), expected);
+            #[cfg(feature = "num-bigint")]
+            test_big(r, e, expected);
+        }
+
+        #[cfg(feature = "num-bigint")]
+        #[allow(clippy::needless_borrow)]
+        fn test_big(r: Rational64, e: i32, expected: Rational64) {
+            let r = BigRational::new_raw(r.numer.into(), r.denom.into());
+            let expected = BigRational::new_raw(expected.numer.into(), expected.denom.into());
+            assert_eq!((&r).pow(e), expected);
+            assert_eq!(Pow::pow(r.clone(), e), expected);
+            assert_eq!(Pow::pow(r.clone(), &e), expected);
+            assert_eq!(Pow::pow(&r, e), expected);
+            assert_eq!(Pow::pow(&r, &e), expected);
+        }
+
+        test(_1_2, 2, Ratio::new(1, 4));
+        test(_1_2, -2, Ratio::new(4, 1));
+        test(_1, 1, _1);
+        test(_1, i32::MAX, _1);
+        test(_1, i32::MIN, _1);
+        test(_NEG1_2, 2, _1_2.pow(2i32));
+        test(_NEG1_2, 3, -_1_2.pow(3i32));
+        test(_3_2, 0, _1);
+        test(_3_2, -1, _3_2.recip());
+        test(_3_2, 3, Ratio::new(27, 8));
+
+        // The magnitude of `MIN` exponents doesn't fit the signed type.
+        let neg1 = Ratio::from(-1);
+        test(neg1, i32::MIN, _1);
+        test(neg1, i32::MIN + 1, neg1);
+        assert_eq!(Pow::pow(neg1, i16::MIN), _1);
+        #[cfg(feature = "num-bigint")]
+        assert_eq!(
+            Pow::pow(to_big(_1_2), i8::MIN),
+            BigRational::from_integer(BigInt::one() << 128)
+        );
+        assert_eq!(Pow::pow(_2, i8::MIN + 66), Ratio::new(1, 1 << 62));
+    }
+
+    #[test]
+    fn test_checked_pow() {
+        use crate::CheckedPow;
+
+        fn cube<R: CheckedPow<i32>>(r: &R) -> Option<R> {
+            r.checked_pow(3)
+        }
+
+        assert_eq!(cube(&_NEG1_2), Some(Ratio::new(-1, 8)));
+        assert_eq!(cube(&Ratio::new(6i8, 1)), None);
+        assert_eq!(cube(&Ratio::new(1i8, 6)), None);
+        assert_eq!(cube(&Ratio::new(-6i8, 5)), None);
+        assert_eq!(cube(&Ratio::new(5u8, 4)), Some(Ratio::new(125, 64)));
+        #[cfg(feature = "num-bigint")]
+        assert_eq!(cube(&to_big(_3_2)), Some(to_big(Ratio::new(27, 8))));
+
+        assert_eq!(_3_2.checked_pow(0u8), Some(_1));
+        assert_eq!(_3_2.checked_pow(-1i64), Some(_2_3));
+        assert_eq!(_NEG1_2.checked_pow(-3i32), Some(-_8));
+        assert_eq!(_0.checked_pow(-1i32), None);
+        assert_eq!(_0.checked_pow(3usize), Some(_0));
+        assert_eq!(_1.checked_pow(i32::MIN), Some(_1));
+        assert_eq!(_NEG1_2.checked_pow(i32::MIN), None);
+        assert_eq!(_2.checked_pow(u128::MAX), None);
+        assert_eq!(
+            Ratio::new(-1i8, 2).checked_pow(-5i32),
+            Some(Ratio::new(-32, 1))
+        );
+        assert_eq!(
+            Ratio::new(-2i8, 1).checked_pow(7i32),
+            Some(Ratio::from(-128))
+        );
+        assert_eq!(Ratio::new(-2i8, 1).checked_pow(-7i32), None);
+    }
+
+    #[test]
+    fn test_pow_checked_detailed() {
+        use crate::CheckedPow;
+
+        for &r in &[_0, _1, _NEG1_2, _3_2, _2, Ratio::new(-5, 7)] {
+            for expon in -20..20 {
+                if r.is_zero() && expon < 0 {
+                    continue;
+                }
+                assert_eq!(r.pow_checked_detailed(expon).ok(), r.checked_pow(expon));
+            }
+        }
+
+        assert_eq!(_2.pow_checked_detailed(62), Ok(Ratio::from(1 << 62)));
+        assert_eq!(_2.pow_checked_detailed(-62), Ok(Ratio::new(1, 1 << 62)));
+        // 2^31 * 2^32 overflows at bit 5, and squaring to 2^64 at bit 6
+        assert_eq!(_2.pow_checked_detailed(63), Err(5));
+        assert_eq!(_2.pow_checked_detailed(64), Err(6));
+        // 3^36 fits, but 3^64 doesn't
+        assert_eq!(
+            _3_2.pow_checked_detailed(36),
+            Ok(Ratio::new(3i64.pow(36), 1 << 36))
+        );
+        assert_eq!(_3_2.pow_checked_detailed(100), Err(6));
+        assert_eq!(_1.pow_checked_detailed(i32::MIN), Ok(_1));
+
+        // (-2)^63 is MIN, which fits, but its reciprocal doesn't
+        assert_eq!(_NEG2.pow_checked_detailed(63), Ok(_MIN));
+        assert_eq!(_NEG2.pow_checked_detailed(-63), Err(6));
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn test_pow_checked_detailed_zero() {
+        let _ = _0.pow_checked_detailed(-1);
+    }
+
+    #[test]
+    fn test_from_str_raw() {
+        let r = Ratio::<i64>::from_str_raw("4/8").unwrap();
+        assert_eq!((r.numer, r.denom), (4, 8));
+        assert_eq!(r, _1_2);
+
+        let r = Ratio::<i64>::from_str_raw("3/-6").unwrap();
+        assert_eq!((r.numer, r.denom), (3, -6));
+        let r = Ratio::<i64>::from_str_raw("-5").unwrap();
+        assert_eq!((r.numer, r.denom), (-5, 1));
+
+        for s in &["1/0", "0/0", "", "1/", "a/2", "1/2/3"] {
+            assert!(Ratio::<i64>::from_str_raw(s).is_err(), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_from_str_lenient() {
+        assert_eq!(Ratio::from_str_lenient("5/"), Ok(Ratio::from(5)));
+        assert_eq!(Ratio::from_str_lenient("-5/"), Ok(Ratio::from(-5)));
+        assert_eq!(Ratio::from_str_lenient("5"), Ok(Ratio::from(5)));
+        assert_eq!(Ratio::from_str_lenient("4/8"), Ok(_1_2));
+        // the strict parser still rejects it
+        assert!(Rational64::from_str("5/").is_err());
+
+        for s in &["/", "", "5//", "/5/", "1/0/", "a/"] {
+            assert!(Rational64::from_str_lenient(s).is_err(), "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_to_f64_with_error() {
+        fn test(r: Rational64) {
+            let (value, error) = r.to_f64_with_error().unwrap();
+            assert_eq!(Some(value), r.to_f64());
+            assert!(error >= _0);
+            let exact = Ratio::from_f64_if_exact_i64(value).unwrap();
+            assert!(exact - error <= r && r <= exact + error);
+            assert!(error == (r - exact).abs());
+        }
+
+        test(_1_3);
+        test(_NEG1_3);
+        test(Ratio::new(2, 3));
+        test(Ratio::new(1, 10));
+        test(Ratio::new((1 << 60) + 1, 1 << 60));
+
+        assert_eq!(_1_2.to_f64_with_error(), Some((0.5, _0)));
+        assert_eq!(_NEG2.to_f64_with_error(), Some((-2.0, _0)));
+
+        // 1/3 is 0x5555...55p-54 to the nearest f64, off by 1/(3 * 2^54)
+        let (value, error) = Ratio::new(1u64, 3).to_f64_with_error().unwrap();
+        assert_eq!(value, 1.0 / 3.0);
+        assert_eq!(error, Ratio::new(1, 3 << 54));
+        // that doesn't fit in 32 bits
+        assert_eq!(Ratio::new(1i32, 3).to_f64_with_error(), None);
+        // 2^63 is the nearest float, which doesn't fit in an i64
+        assert_eq!(_MAX.to_f64_with_error(), None);
+        assert_eq!(_MIN.to_f64_with_error(), Some((-(2f64.powi(63)), _0)));
+        assert_eq!(
+            Ratio::new(3u8, 4).to_f64_with_error(),
+            Some((0.75, Ratio::zero()))
+        );
+        assert_eq!(Ratio::<i32>::new_raw(1, 0).to_f64_with_error(), None);
+
+        #[cfg(feature = "num-bigint")]
+        {
+            let (value, error) = to_big(_2_3).to_f64_with_error().unwrap();
+            assert_eq!(Ratio::from_float(value).unwrap() + error, to_big(_2_3));
+            let (value, error) = to_big(_MAX).to_f64_with_error().unwrap();
+            assert_eq!(value, 2f64.powi(63));
+            assert_eq!(error, to_big(_1));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "num-bigint")]
+    fn test_to_f64_round() {
+        use crate::RoundingMode::{Down, Nearest, TowardZero, Up};
+
+        let third = _1_3;
+        let (down, up) = (third.to_f64_round(Down), third.to_f64_round(Up));
+        assert!(down < up);
+        assert_eq!(f64::from_bits(down.to_bits() + 1), up);
+        assert!(to_big(third) > Ratio::from_float(down).unwrap());
+        assert!(to_big(third) < Ratio::from_float(up).unwrap());
+        assert_eq!(third.to_f64_round(Nearest), third.to_f64().unwrap());
+        assert_eq!(third.to_f64_round(TowardZero), down);
+
+        let neg = -third;
+        assert_eq!(neg.to_f64_round(Down), -up);
+        assert_eq!(neg.to_f64_round(Up), -down);
+        assert_eq!(neg.to_f64_round(TowardZero), -down);
+
+        for mode in &[Nearest, Down, Up, TowardZero] {
+            assert_eq!(_1_2.to_f64_round(*mode), 0.5);
+            assert_eq!(_NEG2.to_f64_round(*mode), -2.0);
+            assert_eq!(_0.to_f64_round(*mode), 0.0);
+        }
+
+        // i64::MAX is not representable, and is just below 2^63
+        let max = _MAX.to_f64_round(Down);
+        assert!(max < 9223372036854775808.0);
+        assert_eq!(_MAX.to_f64_round(Up), 9223372036854775808.0);
+
+        // beyond the range of f64
+        let huge = BigRational::from_integer(BigInt::one() << 1024);
+        assert_eq!(huge.to_f64_round(Down), f64::MAX);
+        assert_eq!(huge.to_f64_round(Up), f64::INFINITY);
+        assert_eq!((-huge).to_f64_round(TowardZero), -f64::MAX);
+
+        // below the smallest subnormal
+        let tiny = BigRational::new(BigInt::one(), BigInt::one() << 1100);
+        assert_eq!(tiny.to_f64_round(Down), 0.0);
+        assert_eq!(tiny.to_f64_round(Up), f64::from_bits(1));
+        assert_eq!((-tiny).to_f64_round(Down), -f64::from_bits(1));
+
+        assert!(Ratio::<i32>::new_raw(0, 0).to_f64_round(Up).is_nan());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_clear_denominators() {
+        let (numers, denom) = Ratio::clear_denominators(&[_1_2, Ratio::new(1, 3)]);
+        assert_eq!((&numers[..], denom), (&[3, 2][..], 6));
+
+        let (numers, denom) = Ratio::clear_denominators(&[_NEG1_2, _2, Ratio::new(5, 4)]);
+        assert_eq!((&numers[..], denom), (&[-2, 8, 5][..], 4));
+
+        let (numers, denom) = Ratio::<i32>::clear_denominators(&[]);
+        assert!(numers.is_empty());
+        assert_eq!(denom, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_content_and_primitive() {
+        let (content, primitive) = Ratio::content_and_primitive(&[_1_2, Ratio::new(3, 4)]);
+        assert_eq!(content, Ratio::new(1, 4));
+        assert_eq!(primitive, [2, 3]);
+
+        let coeffs = [Ratio::new(4, 3), _0, Ratio::new(-2, 9)];
+        let (content, primitive) = Ratio::content_and_primitive(&coeffs);
+        assert_eq!(content, Ratio::new(2, 9));
+        assert_eq!(primitive, [6, 0, -1]);
+        for (c, p) in coeffs.iter().zip(&primitive) {
+            assert_eq!(*c, content * p);
+        }
+
+        let (content, primitive) = Ratio::content_and_primitive(&[_0, _0]);
+        assert_eq!((content, &primitive[..]), (_0, &[0, 0][..]));
+        let (content, primitive) = Ratio::<i64>::content_and_primitive(&[]);
+        assert_eq!(content, _0);
+        assert!(primitive.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_to_f64_vec() {
+        let ratios = [_0, _1_2, _NEG1_2, _3_2, _MIN, _MAX, _MIN_P1];
+        let floats = Ratio::to_f64_vec(&ratios);
+        assert_eq!(floats.len(), ratios.len());
+        for (r, f) in ratios.iter().zip(&floats) {
+            assert_eq!(r.to_f64(), Some(*f));
+        }
+        assert!(Ratio::<i64>::to_f64_vec(&[]).is_empty());
+
+        let nan = Ratio::to_f64_vec(&[Ratio::<i32>::new_raw(0, 0), Ratio::from(2)]);
+        assert!(nan[0].is_nan());
+        assert_eq!(nan[1], 2.0);
+
+        #[cfg(feature = "num-bigint")]
+        assert_eq!(
+            Ratio::to_f64_vec(&[to_big(_1_2), to_big(_NEG2)]),
+            [0.5, -2.0]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_to_grouped_string() {
+        assert_eq!(
+            Ratio::new(1234567, 1000).to_grouped_string(','),
+            "1,234,567/1,000"
+        );
+        assert_eq!(
+            Ratio::new(-1234567, 100).to_grouped_string('_'),
+            "-1_234_567/100"
+        );
+        assert_eq!(Ratio::from(123456).to_grouped_string(','), "123,456");
+        assert_eq!(Ratio::new(12, 7).to_grouped_string(','), "12/7");
+        assert_eq!(Ratio::new_raw(0, 1000).to_grouped_string(','), "0");
+        assert_eq!(
+            Ratio::<i64>::new_raw(1000, -3).to_grouped_string(' '),
+            "1 000/-3"
+        );
+        assert_eq!(_MIN.to_grouped_string(','), "-9,223,372,036,854,775,808");
+
+        #[cfg(feature = "num-bigint")]
+        assert_eq!(
+            BigRational::new(BigInt::from(10).pow(12u32), 7.into()).to_grouped_string('.'),
+            "1.000.000.000.000/7"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_to_engineering_string() {
+        assert_eq!(Ratio::new(1, 1000000).to_engineering_string(3), "1.00e-6");
+        assert_eq!(Ratio::from(12345).to_engineering_string(3), "12.3e3");
+        assert_eq!(Ratio::new(-2, 3).to_engineering_string(4), "-666.7e-3");
+        assert_eq!(Ratio::new(1, 8).to_engineering_string(2), "130e-3");
+        assert_eq!(Ratio::new(7, 2).to_engineering_string(1), "4e0");
+        assert_eq!(Ratio::new(24999, 25).to_engineering_string(4), "1.000e3");
+        assert_eq!(Ratio::new(1, 3).to_engineering_string(1), "300e-3");
+        assert_eq!(Ratio::new_raw(1, -1000).to_engineering_string(2), "-1.0e-3");
+        assert_eq!(_0.to_engineering_string(3), "0.00e0");
+        assert_eq!(_MIN.to_engineering_string(3), "-9.22e18");
+        assert_eq!(Ratio::new(5u8, 7).to_engineering_string(5), "714.29e-3");
+
+        // digits of denominators close to `T::MAX`
+        assert_eq!(Ratio::new(29u8, 30).to_engineering_string(2), "970e-3");
+        assert_eq!(Ratio::new(1u8, 255).to_engineering_string(3), "3.92e-3");
+        assert_eq!(
+            Ratio::new(1i64, i64::MAX).to_engineering_string(3),
+            "108e-21"
+        );
+        assert_eq!(
+            Ratio::new(-1i64, i64::MAX).to_engineering_string(2),
+            "-110e-21"
+        );
+        assert_eq!(_MAX_M1.recip().to_engineering_string(3), "108e-21");
+        assert_eq!(
+            Ratio::new(i64::MAX - 1, i64::MAX).to_engineering_string(3),
+            "1.00e0"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_to_string_radix() {
+        assert_eq!(Ratio::new(255, 16).to_string_radix(16), "ff/10");
+        assert_eq!(Ratio::new(-255, 16).to_string_radix(16), "-ff/10");
+        assert_eq!(Ratio::from(-4096).to_string_radix(16), "-1000");
+        assert_eq!(Ratio::new(35, 36).to_string_radix(36), "z/10");
+        assert_eq!(Ratio::new(1295, 38).to_string_radix(36), "zz/12");
+        assert_eq!(_NEG1_2.to_string_radix(2), "-1/10");
+        assert_eq!(_0.to_string_radix(7), "0");
+        assert_eq!(Ratio::new_raw(0, 5).to_string_radix(7), "0");
+        assert_eq!(_MIN.to_string_radix(16), "-8000000000000000");
+        assert_eq!(Ratio::new(200u8, 3).to_string_radix(16), "c8/3");
+
+        for &r in &[_1_2, _NEG1_2, _3_2, _MIN, _MAX, Ratio::new(12345, 678)] {
+            for &radix in &[2, 10, 16, 36] {
+                let mut s = r.to_string_radix(radix);
+                if r.is_integer() {
+                    s.push_str("/1");
+                }
+                assert_eq!(Rational64::from_str_radix(&s, radix), Ok(r), "{}", s);
+            }
+        }
+        assert_eq!(_3_2.to_string_radix(10), format!("{}", _3_2));
 
-        assert_eq!(_3_2.recip(), _2_3);
-        assert_eq!(_NEG1_2.recip(), _NEG2);
-        assert_eq!(_NEG1_2.recip().denom(), &1);
+        #[cfg(feature = "num-bigint")]
+        assert_eq!(to_big(Ratio::new(-255, 16)).to_string_radix(36), "-73/g");
     }
 
     #[test]
-    #[should_panic(expected = "division by zero")]
-    fn test_recip_fail() {
-        let _a = Ratio::new(0, 1).recip();
+    #[cfg(feature = "std")]
+    #[should_panic(expected = "radix must be in the range 2..=36")]
+    fn test_to_string_radix_invalid() {
+        let _ = _1_2.to_string_radix(37);
     }
 
     #[test]
-    fn test_pow() {
-        fn test(r: Rational64, e: i32, expected: Rational64) {
-            assert_eq!(r.pow(e), expected);
-            assert_eq!(Pow::pow(r, e), expected);
-            assert_eq!(Pow::pow(r, &e), expected);
-            assert_eq!(Pow::pow(&r, e), expected);
-            assert_eq!(Pow::pow(&r, &e), expected);
-            #[cfg(feature = "num-bigint")]
-            test_big(r, e, expected);
+    #[cfg(feature = "std")]
+    fn test_write_to() {
+        use std::string::{String, ToString};
+
+        let mut buf = String::new();
+        for r in &[_0, _2, _1_2, _NEG1_2, _NEG2, _3_2, _MIN, _MAX_M1] {
+            buf.clear();
+            r.write_to(&mut buf).unwrap();
+            assert_eq!(buf, r.to_string());
         }
 
+        buf.clear();
+        _1_2.write_to(&mut buf).unwrap();
+        buf.push(' ');
+        _NEG2.write_to(&mut buf).unwrap();
+        assert_eq!(buf, "1/2 -2");
+
         #[cfg(feature = "num-bigint")]
-        fn test_big(r: Rational64, e: i32, expected: Rational64) {
-            let r = BigRational::new_raw(r.numer.into(), r.denom.into());
-            let expected = BigRational::new_raw(expected.numer.into(), expected.denom.into());
-            assert_eq!((&r).pow(e), expected);
-            assert_eq!(Pow::pow(r.clone(), e), expected);
-            assert_eq!(Pow::pow(r.clone(), &e), expected);
-            assert_eq!(Pow::pow(&r, e), expected);
-            assert_eq!(Pow::pow(&r, &e), expected);
+        {
+            buf.clear();
+            to_big(_NEG1_2).write_to(&mut buf).unwrap();
+            assert_eq!(buf, "-1/2");
         }
-
-        test(_1_2, 2, Ratio::new(1, 4));
-        test(_1_2, -2, Ratio::new(4, 1));
-        test(_1, 1, _1);
-        test(_1, i32::MAX, _1);
-        test(_1, i32::MIN, _1);
-        test(_NEG1_2, 2, _1_2.pow(2i32));
-        test(_NEG1_2, 3, -_1_2.pow(3i32));
-        test(_3_2, 0, _1);
-        test(_3_2, -1, _3_2.recip());
-        test(_3_2, 3, Ratio::new(27, 8));
     }
 
     #[test]
@@ -2781,6 +7264,24 @@ mod test {
         test(_2, "2".to_string());
         test(_NEG1_2, "-1/2".to_string());
     }
+    #[test]
+    fn test_from_str_signs() {
+        fn test(s: &str, expected: Rational64) {
+            let parsed: Rational64 = FromStr::from_str(s).unwrap();
+            assert_eq!(parsed, expected);
+            assert!(parsed.denom > 0);
+            let parsed: Rational64 = Num::from_str_radix(s, 10).unwrap();
+            assert_eq!(parsed, expected);
+            assert!(parsed.denom > 0);
+        }
+        let _3_4 = Ratio::new(3, 4);
+        test("3/4", _3_4);
+        test("-3/4", -_3_4);
+        test("3/-4", -_3_4);
+        test("-3/-4", _3_4);
+        test("+6/-8", -_3_4);
+    }
+
     #[test]
     fn test_from_str_fail() {
         fn test(s: &str) {
@@ -2794,8 +7295,114 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_from_str_overflow() {
+        use crate::RatioErrorKind::{Overflow, ParseError, ZeroDenominator};
+        use crate::{ParseRatioError, RatioErrorKind};
+
+        fn kind<T: Clone + Integer + core::fmt::Debug>(
+            r: Result<Ratio<T>, ParseRatioError>,
+        ) -> RatioErrorKind {
+            r.unwrap_err().kind
+        }
+
+        assert_eq!(
+            kind(Ratio::<i32>::from_str_radix("1ffffffff/2", 16)),
+            Overflow
+        );
+        assert_eq!(
+            kind(Ratio::<i32>::from_str_radix("-ffffffffff/2", 16)),
+            Overflow
+        );
+        assert_eq!(
+            kind(Ratio::<i32>::from_str_radix("1/+100000000", 16)),
+            Overflow
+        );
+        assert_eq!(kind(Ratio::<u8>::from_str_radix("-1/2", 16)), Overflow);
+        assert_eq!(kind(Ratio::<i32>::from_str_radix("1fg/2", 16)), ParseError);
+        assert_eq!(kind(Ratio::<i32>::from_str_radix("12", 16)), ParseError);
+        assert_eq!(kind(Ratio::<i32>::from_str_radix("1/", 16)), ParseError);
+        assert_eq!(
+            kind(Ratio::<i32>::from_str_radix("1/0", 16)),
+            ZeroDenominator
+        );
+        assert_eq!(
+            Ratio::<i32>::from_str_radix("7fffffff/2", 16),
+            Ok(Ratio::new(i32::MAX, 2))
+        );
+
+        assert_eq!(
+            kind("9223372036854775808/2".parse::<Rational64>()),
+            Overflow
+        );
+        assert_eq!(
+            kind("1/-9223372036854775809".parse::<Rational64>()),
+            Overflow
+        );
+        assert_eq!(kind("1/a".parse::<Rational64>()), ParseError);
+        assert_eq!(kind(Ratio::<i8>::from_str_raw("128")), Overflow);
+
+        #[cfg(feature = "std")]
+        assert_eq!(
+            std::string::ToString::to_string(&"128".parse::<Ratio<i8>>().unwrap_err()),
+            "integer out of range"
+        );
+
+        // normalizing the sign of an unreduced MIN
+        assert_eq!(
+            kind("-9223372036854775808/-1".parse::<Rational64>()),
+            Overflow
+        );
+        assert_eq!(
+            kind("1/-9223372036854775808".parse::<Rational64>()),
+            Overflow
+        );
+        assert_eq!(kind(Ratio::<i8>::from_str_radix("-80/-3", 16)), Overflow);
+        assert_eq!(
+            "-9223372036854775808/-2".parse(),
+            Ok(Ratio::from(1i64 << 62))
+        );
+        assert_eq!("-9223372036854775808/-9223372036854775808".parse(), Ok(_1));
+        assert_eq!("0/-9223372036854775808".parse(), Ok(_0));
+        assert_eq!("-9223372036854775808/1".parse(), Ok(_MIN));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_from_str_extremes() {
+        let parts = [
+            "0", "1", "-1", "2", "-2", "+3", "-64", "127", "-128", "128", "-129", "-0", "x", "",
+        ];
+        for n in &parts {
+            for d in &parts {
+                let s = format!("{}/{}", n, d);
+                // Both parts must fit, and then the reduced value computed in a wider type.
+                let narrow = s
+                    .parse::<Ratio<i128>>()
+                    .ok()
+                    .filter(|_| n.parse::<i8>().is_ok() && d.parse::<i8>().is_ok())
+                    .and_then(|r| Some((i8::try_from(r.numer).ok()?, i8::try_from(r.denom).ok()?)));
+                assert_eq!(
+                    s.parse::<Ratio<i8>>().ok().map(Ratio::into_raw),
+                    narrow,
+                    "{}",
+                    s
+                );
+                assert_eq!(
+                    Ratio::<i8>::from_str_radix(&s, 10)
+                        .ok()
+                        .map(Ratio::into_raw),
+                    narrow,
+                    "{}",
+                    s
+                );
+            }
+        }
+    }
+
     #[cfg(feature = "num-bigint")]
     #[test]
+    #[allow(clippy::excessive_precision)]
     fn test_from_float() {
         use num_traits::float::FloatCore;
         fn test<T: FloatCore>(given: T, (numer, denom): (&str, &str)) {
@@ -2843,9 +7450,77 @@ mod test {
 
     #[cfg(feature = "num-bigint")]
     #[test]
-    fn test_from_float_fail() {
-        use core::{f32, f64};
+    fn test_to_f64_with_residual() {
+        fn test(r: BigRational) {
+            let (value, residual) = r.to_f64_with_residual().unwrap();
+            assert_eq!(Some(value), r.to_f64());
+            assert_eq!(Ratio::from_float(value).unwrap() + residual, r);
+        }
+
+        test(to_big(_1_2));
+        test(to_big(_NEG1_3));
+        test(to_big(_2_3));
+        test(BigRational::new(
+            "1234567890987654321234567890".parse().unwrap(),
+            "987654321234567890987654321".parse().unwrap(),
+        ));
+
+        let (value, residual) = to_big(_1_2).to_f64_with_residual().unwrap();
+        assert_eq!(value, 0.5);
+        assert!(residual.is_zero());
+
+        assert_eq!(
+            BigRational::from(BigInt::one() << 1050).to_f64_with_residual(),
+            None
+        );
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn test_clamp_into_i64() {
+        // in range, so exact
+        assert!(BigRational::new_raw(6.into(), (-4).into())
+            .clamp_into_i64()
+            .strict_eq(&Ratio::new(-3, 2)));
+        assert_eq!(to_big(_MIN).clamp_into_i64(), _MIN);
+        assert_eq!(to_big(_MAX).clamp_into_i64(), _MAX);
+        assert_eq!(
+            to_big(Ratio::new(1, i64::MAX)).clamp_into_i64(),
+            Ratio::new(1, i64::MAX)
+        );
+
+        // out of range, so saturated
+        let big = BigRational::from(BigInt::one() << 70);
+        assert_eq!(big.clamp_into_i64(), _MAX);
+        assert_eq!((-&big).clamp_into_i64(), _MIN);
+        assert_eq!(big.recip().clamp_into_i64(), _0);
+        assert_eq!((to_big(_MAX) + to_big(_1_2)).clamp_into_i64(), _MAX);
+
+        // in range, but the parts are too large
+        let e30 = BigInt::from(10).pow(30u32);
+        let third = BigRational::new(&e30 + 1, e30 * 3);
+        assert_eq!(third.clamp_into_i64(), _1_3);
+        assert_eq!((-third).clamp_into_i64(), _NEG1_3);
+
+        // just above 2^62 + 1/2, where no denominator other than 1 keeps the numerator in range
+        let x = BigRational::from(BigInt::one() << 62)
+            + to_big(_1_2)
+            + BigRational::new(BigInt::one(), BigInt::one() << 100);
+        let expected = Ratio::from_integer((1 << 62) + 1);
+        assert_eq!(x.clamp_into_i64(), expected);
+        assert_eq!((-x).clamp_into_i64(), -expected);
+
+        // above 1, the closer of the two candidates
+        let x = BigRational::new(BigInt::from(i64::MAX) * 7 + 3, BigInt::from(i64::MAX) * 2);
+        let r = x.clamp_into_i64();
+        assert!(r.numer() <= &i64::MAX);
+        let err = (to_big(r) - &x).abs();
+        assert!(err <= (to_big(Ratio::new(7, 2)) - &x).abs());
+    }
 
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn test_from_float_fail() {
         assert_eq!(Ratio::from_float(f32::NAN), None);
         assert_eq!(Ratio::from_float(f32::INFINITY), None);
         assert_eq!(Ratio::from_float(f32::NEG_INFINITY), None);
@@ -2854,6 +7529,34 @@ mod test {
         assert_eq!(Ratio::from_float(f64::NEG_INFINITY), None);
     }
 
+    #[test]
+    fn test_from_f64_if_exact_i64() {
+        fn test(x: f64, expected: Option<(i64, i64)>) {
+            let r = Rational64::from_f64_if_exact_i64(x);
+            assert_eq!(r.map(Ratio::into_raw), expected);
+            if let Some(r) = r {
+                assert!(r.is_reduced());
+            }
+        }
+
+        test(0.25, Some((1, 4)));
+        test(-1.5, Some((-3, 2)));
+        test(3.0, Some((3, 1)));
+        test(0.0, Some((0, 1)));
+        test(-0.0, Some((0, 1)));
+        // 0.1 isn't exactly 1/10, but its dyadic value still fits.
+        test(0.1, Some((3602879701896397, 1 << 55)));
+        test(-9223372036854775808.0, Some((i64::MIN, 1)));
+
+        test(9223372036854775808.0, None);
+        test(1e300, None);
+        test(1e-30, None);
+        test(f64::MIN_POSITIVE, None);
+        test(f64::NAN, None);
+        test(f64::INFINITY, None);
+        test(f64::NEG_INFINITY, None);
+    }
+
     #[test]
     fn test_signed() {
         assert_eq!(_NEG1_2.abs(), _1_2);
@@ -2874,6 +7577,105 @@ mod test {
         assert!(!_0.is_negative());
     }
 
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        should_panic(expected = "attempt to negate with overflow")
+    )]
+    #[cfg_attr(not(debug_assertions), should_panic(expected = "negation overflow"))]
+    fn test_neg_overflow() {
+        let _ = -_MIN;
+    }
+
+    #[test]
+    fn test_checked_neg() {
+        assert_eq!(_1_2.checked_neg(), Some(-_1_2));
+        assert_eq!(_0.checked_neg(), Some(_0));
+        assert_eq!(_MAX.checked_neg(), Some(_MIN_P1));
+        assert_eq!(_MIN.checked_neg(), None);
+        assert_eq!(Ratio::new(1u8, 2).checked_neg(), None);
+        assert_eq!(Ratio::new(0u8, 2).checked_neg(), Some(Ratio::new_raw(0, 2)));
+    }
+
+    #[test]
+    fn test_abs_raw() {
+        for r in &[_1_NEG2, _NEG1_NEG2, _NEG1_2, _1_2] {
+            let abs = r.abs();
+            assert_eq!((abs.numer, abs.denom), (1, 2));
+        }
+        let abs = Ratio::<i64>::new_raw(0, -3).abs();
+        assert_eq!((abs.numer, abs.denom), (0, 3));
+
+        #[cfg(feature = "std")]
+        assert_eq!(format!("{}", Ratio::new_raw(1, -2).abs()), "1/2");
+    }
+
+    #[test]
+    fn test_split_sign() {
+        assert_eq!(_NEG1_2.split_sign(), (-1, _1_2));
+        assert_eq!(_1_NEG2.split_sign(), (-1, _1_2));
+        assert_eq!(_0.split_sign(), (0, _0));
+        assert_eq!(_3_2.split_sign(), (1, _3_2));
+        assert_eq!(_NEG1_NEG2.split_sign(), (1, _1_2));
+    }
+
+    #[test]
+    fn test_with_sign() {
+        for r in &[_NEG1_2, _1_NEG2, _0, _3_2, _NEG1_NEG2, _MIN_P1, _MAX] {
+            let (sign, abs) = r.split_sign();
+            assert_eq!(abs.with_sign(sign < 0), *r);
+        }
+        assert_eq!(_1_2.with_sign(true), _NEG1_2);
+        assert_eq!(_NEG1_2.with_sign(true), _NEG1_2);
+        assert_eq!(_NEG1_2.with_sign(false), _1_2);
+        assert_eq!(_0.with_sign(true), _0);
+    }
+
+    #[test]
+    fn test_denom_inverse_mod() {
+        let two_thirds = Ratio::new(2, 3);
+        let inv = two_thirds.denom_inverse_mod(&7);
+        assert_eq!(inv, Some(5));
+        assert_eq!((two_thirds.numer * inv.unwrap()) % 7, 3);
+
+        assert_eq!(Ratio::new(-2, 3).denom_inverse_mod(&7), Some(5));
+        assert_eq!(Ratio::new_raw(1, -3).denom_inverse_mod(&7), Some(5));
+        assert_eq!(Ratio::new_raw(2, 6).denom_inverse_mod(&7), Some(5));
+        assert_eq!(_2.denom_inverse_mod(&7), Some(1));
+        assert_eq!(Ratio::new(1, 10).denom_inverse_mod(&7), Some(5));
+        assert_eq!(_1_3.denom_inverse_mod(&1), Some(0));
+
+        assert_eq!(_1_3.denom_inverse_mod(&9), None);
+        assert_eq!(_1_3.denom_inverse_mod(&0), None);
+        assert_eq!(_1_3.denom_inverse_mod(&-7), None);
+    }
+
+    #[test]
+    fn test_into_unsigned_parts() {
+        assert_eq!(_NEG1_2.into_unsigned_parts(), (true, 1, 2));
+        assert_eq!(_1_NEG2.into_unsigned_parts(), (true, 1, 2));
+        assert_eq!(_NEG1_NEG2.into_unsigned_parts(), (false, 1, 2));
+        assert_eq!(_3_2.into_unsigned_parts(), (false, 3, 2));
+        assert_eq!(_0.into_unsigned_parts(), (false, 0, 1));
+        assert_eq!(_MIN_P1.into_unsigned_parts(), (true, i64::MAX, 1));
+    }
+
+    #[test]
+    fn test_checked_abs() {
+        assert_eq!(_NEG1_2.checked_abs(), Some(_1_2));
+        assert_eq!(_1_2.checked_abs(), Some(_1_2));
+        assert_eq!(_0.checked_abs(), Some(_0));
+        assert_eq!(_1_NEG2.checked_abs(), Some(_1_2));
+        assert_eq!(_MIN_P1.checked_abs(), Some(-_MIN_P1));
+        assert_eq!(_MIN.checked_abs(), None);
+        assert_eq!(Ratio::new(i8::MIN, 1).checked_abs(), None);
+        assert_eq!(Ratio::new(i8::MIN, 3).checked_abs(), None);
+        assert_eq!(
+            Ratio::new(i8::MIN + 1, 3).checked_abs(),
+            Some(Ratio::new(i8::MAX, 3))
+        );
+    }
+
     #[test]
     #[cfg(feature = "std")]
     fn test_hash() {
@@ -2892,6 +7694,40 @@ mod test {
         assert_eq!(crate::hash(&a), crate::hash(&b));
     }
 
+    #[test]
+    fn test_to_canonical_key() {
+        assert_eq!(_1_2.to_canonical_key(), (1, 2));
+        assert_eq!(_NEG1_2.to_canonical_key(), (-1, 2));
+        assert_eq!(_0.to_canonical_key(), (0, 1));
+
+        let a = Rational64::new_raw(4, 8);
+        let b = Rational64::new_raw(-3, -6);
+        assert_eq!(a, b);
+        assert_eq!(a.to_canonical_key(), b.to_canonical_key());
+        assert_eq!(_1_NEG2.to_canonical_key(), _NEG1_2.to_canonical_key());
+
+        assert_eq!(
+            Rational64::new_raw(i64::MIN, -1).to_canonical_key(),
+            (-(i64::MIN as i128), 1)
+        );
+        assert_eq!(Ratio::new(200u8, 100).to_canonical_key(), (2, 1));
+        assert_eq!(
+            Ratio::new_raw(i128::MIN, i128::MIN).to_canonical_key(),
+            (1, 1)
+        );
+        assert_eq!(
+            Ratio::new_raw(i128::MIN, 2).to_canonical_key(),
+            (i128::MIN / 2, 1)
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "attempt to subtract with overflow")]
+    fn test_to_canonical_key_i128_overflow() {
+        let _ = Ratio::new_raw(i128::MIN, -1).to_canonical_key();
+    }
+
     #[test]
     fn test_into_pair() {
         assert_eq!((0, 1), _0.into());
@@ -2907,6 +7743,21 @@ mod test {
         assert_eq!(_1_NEG2, Ratio::from((1, -2)));
     }
 
+    #[test]
+    fn test_from_pair_raw() {
+        let r = Ratio::from_pair_raw((2, -4));
+        assert_eq!(r.into_raw(), (2, -4));
+        assert_eq!(r, _NEG1_2);
+        assert!(Ratio::from((2, -4)).strict_eq(&_NEG1_2));
+        assert_eq!(Ratio::from_pair_raw((0u8, 7)).into_raw(), (0, 7));
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "denominator == 0"))]
+    fn test_from_pair_raw_zero() {
+        let _ = Ratio::from_pair_raw((1, 0));
+    }
+
     #[test]
     fn ratio_iter_sum() {
         // generic function to assure the iter method can be called
@@ -2928,6 +7779,107 @@ mod test {
         assert_eq!(sums[0], sums[2]);
     }
 
+    #[test]
+    fn ratio_iter_collect() {
+        let total: Rational64 = [_1_2, _1_2].iter().cloned().collect();
+        assert!(total.strict_eq(&_1));
+        let total: Rational64 = core::iter::empty().collect();
+        assert!(total.strict_eq(&_0));
+        let total: Ratio<u8> = (1..=4).map(|d| Ratio::new(1, d)).collect();
+        assert_eq!(total, Ratio::new(25, 12));
+    }
+
+    #[test]
+    fn ratio_iter_sum_pairwise() {
+        let mut nums = [_1; 30];
+        for (i, r) in nums.iter_mut().enumerate() {
+            let i = i as i64;
+            *r = Ratio::new(if i % 3 == 0 { -i } else { i + 1 }, i % 7 + 1);
+        }
+        let naive: Rational64 = nums.iter().sum();
+        assert!(Ratio::sum_pairwise(&nums).strict_eq(&naive));
+
+        for len in 0..8 {
+            let slice = &[_1_2, _NEG2, _3_2, _2_3, _1_3, _NEG1_2, _2][..len.min(7)];
+            let naive: Rational64 = slice.iter().sum();
+            assert_eq!(Ratio::sum_pairwise(slice), naive);
+        }
+
+        #[cfg(feature = "num-bigint")]
+        {
+            let mut idx = [0i64; 64];
+            for (i, x) in idx.iter_mut().enumerate() {
+                *x = i as i64;
+            }
+            let nums = idx.map(|i| to_big(Ratio::new(i * 7 - 200, i * 5 + 11)));
+            let naive: BigRational = nums.iter().sum();
+            assert_eq!(Ratio::sum_pairwise(&nums), naive);
+        }
+    }
+
+    #[test]
+    fn ratio_iter_sum_common_denom() {
+        let mut nums = [_1; 30];
+        for (i, r) in nums.iter_mut().enumerate() {
+            let i = i as i64;
+            *r = Ratio::new(if i % 3 == 0 { -i } else { i + 1 }, i % 7 + 1);
+        }
+        let naive: Rational64 = nums.iter().sum();
+        assert_eq!(Ratio::sum_common_denom(&nums), naive);
+        assert!(Ratio::sum_common_denom(&nums).strict_eq(&naive));
+
+        assert_eq!(Ratio::sum_common_denom(&[] as &[Rational64]), _0);
+        assert_eq!(Ratio::sum_common_denom(&[_1_2, _1_2]), _1);
+        assert_eq!(Ratio::sum_common_denom(&[_1_2, _NEG1_2]), _0);
+        assert_eq!(Ratio::sum_common_denom(&[_1_NEG2, _1_3]), Ratio::new(-1, 6));
+
+        #[cfg(feature = "num-bigint")]
+        {
+            let nums = nums.map(to_big);
+            let naive: BigRational = nums.iter().sum();
+            assert_eq!(Ratio::sum_common_denom(&nums), naive);
+        }
+    }
+
+    #[test]
+    fn ratio_iter_checked_sum() {
+        let nums = [_1_2, _1_3, _NEG1_2, _2];
+        let expected = Ratio::new(7, 3);
+        assert_eq!(Ratio::checked_sum(nums.iter().cloned()), Some(expected));
+        assert_eq!(Ratio::checked_sum_ref(&nums), Some(expected));
+        assert_eq!(Ratio::checked_sum_ref(nums.iter()), Some(expected));
+        assert_eq!(Ratio::checked_sum_ref(&[] as &[Rational64]), Some(_0));
+
+        let nums = [_MAX, _1, _NEG2];
+        assert_eq!(Ratio::checked_sum(nums.iter().cloned()), None);
+        assert_eq!(Ratio::checked_sum_ref(&nums), None);
+    }
+
+    #[test]
+    fn ratio_iter_checked_sum_ext() {
+        use crate::CheckedSumExt;
+
+        let nums = [_1_2, _1_3, _NEG1_2, _2];
+        let total: Option<Rational64> = nums.iter().cloned().collect_checked_sum();
+        assert_eq!(total, Some(Ratio::new(7, 3)));
+        assert_eq!(
+            nums.iter().cloned().collect_checked_product(),
+            Some(Ratio::new(-1, 6))
+        );
+        assert_eq!(
+            core::iter::empty::<Rational64>().collect_checked_sum(),
+            Some(_0)
+        );
+        assert_eq!(
+            core::iter::empty::<Rational64>().collect_checked_product(),
+            Some(_1)
+        );
+
+        let total: Option<Rational64> = [_MAX, _1, _NEG2].iter().cloned().collect_checked_sum();
+        assert_eq!(total, None);
+        assert_eq!([_MAX, _2].iter().cloned().collect_checked_product(), None);
+    }
+
     #[test]
     fn ratio_iter_product() {
         // generic function to assure the iter method can be called
@@ -2954,6 +7906,35 @@ mod test {
         assert_eq!(products[0], products[2]);
     }
 
+    #[test]
+    fn ratio_iter_product_balanced() {
+        // telescoping product of (n+1)/(n+2), which stays small either way
+        let mut nums = [_1; 50];
+        for (i, r) in nums.iter_mut().enumerate() {
+            *r = Ratio::new(i as i64 + 1, i as i64 + 2);
+        }
+        let naive: Rational64 = nums.iter().product();
+        assert_eq!(Ratio::product_balanced(&nums), naive);
+        assert_eq!(naive, Ratio::new(1, 51));
+
+        for len in 0..8 {
+            let slice = &[_1_2, _NEG2, _3_2, _2_3, _1_3, _NEG1_2, _2][..len.min(7)];
+            let naive: Rational64 = slice.iter().product();
+            assert_eq!(Ratio::product_balanced(slice), naive);
+        }
+
+        #[cfg(feature = "num-bigint")]
+        {
+            let mut idx = [0i64; 64];
+            for (i, x) in idx.iter_mut().enumerate() {
+                *x = i as i64;
+            }
+            let nums = idx.map(|i| to_big(Ratio::new(i * 7 + 3, i * 5 + 11)));
+            let naive: BigRational = nums.iter().product();
+            assert_eq!(Ratio::product_balanced(&nums), naive);
+        }
+    }
+
     #[test]
     fn test_num_zero() {
         let zero = Rational64::zero();
@@ -3038,11 +8019,11 @@ mod test {
         );
         assert_eq!(
             BigRational::from(BigInt::one() << 1050).to_f64(),
-            Some(core::f64::INFINITY)
+            Some(f64::INFINITY)
         );
         assert_eq!(
             BigRational::from((-BigInt::one()) << 1050).to_f64(),
-            Some(core::f64::NEG_INFINITY)
+            Some(f64::NEG_INFINITY)
         );
         assert_eq!(
             BigRational::new(
@@ -3054,16 +8035,24 @@ mod test {
         );
         assert_eq!(
             BigRational::new_raw(BigInt::one(), BigInt::zero()).to_f64(),
-            Some(core::f64::INFINITY)
+            Some(f64::INFINITY)
         );
         assert_eq!(
             BigRational::new_raw(-BigInt::one(), BigInt::zero()).to_f64(),
-            Some(core::f64::NEG_INFINITY)
+            Some(f64::NEG_INFINITY)
         );
         assert_eq!(
             BigRational::new_raw(BigInt::zero(), BigInt::zero()).to_f64(),
             None
         );
+
+        // both parts are far outside the f64 range, but their ratio isn't
+        let huge: BigInt = BigInt::from(10).pow(400u32);
+        let third = BigRational::new(huge.clone() + 1, huge.clone() * 3);
+        assert_eq!(third.to_f64(), Some(1.0 / 3.0));
+        assert_eq!(third.to_f32(), Some(1.0 / 3.0));
+        let r = BigRational::new_raw(huge.clone() * -2i32, huge);
+        assert_eq!(r.to_f64(), Some(-2.0));
     }
 
     #[test]
@@ -3082,20 +8071,94 @@ mod test {
             Rational64::new((1 << 60) + (1 << 8), 1 << 60).to_f64(),
             Some(1.0000000000000002f64),
         );
-        assert_eq!(
-            Ratio::<i32>::new_raw(1, 0).to_f64(),
-            Some(core::f64::INFINITY)
-        );
+        assert_eq!(Ratio::<i32>::new_raw(1, 0).to_f64(), Some(f64::INFINITY));
         assert_eq!(
             Ratio::<i32>::new_raw(-1, 0).to_f64(),
-            Some(core::f64::NEG_INFINITY)
+            Some(f64::NEG_INFINITY)
         );
         assert_eq!(Ratio::<i32>::new_raw(0, 0).to_f64(), None);
+
+        // small parts are exact in floats, so this is the correctly rounded quotient
+        for numer in -50..=50 {
+            for denom in 1..=50 {
+                let r = Ratio::new_raw(numer, denom);
+                assert_eq!(r.to_f64(), Some(numer as f64 / denom as f64));
+                assert_eq!(r.to_f32(), Some(numer as f32 / denom as f32));
+            }
+        }
+    }
+
+    #[test]
+    fn test_ratio_to_f32() {
+        assert_eq!(Ratio::<u8>::new(1, 2).to_f32(), Some(0.5f32));
+        assert_eq!(Rational64::new(1, -2).to_f32(), Some(-0.5f32));
+        assert_eq!(Rational64::new(0, -2).to_f32(), Some(-0.0f32));
+        assert_eq!(Rational64::new(1, 3).to_f32(), Some(1.0f32 / 3.0));
+        assert_eq!(
+            Ratio::<i32>::new(i32::MAX, 3).to_f32(),
+            Some(715827882.3f32)
+        );
+
+        // Large magnitudes are scaled, not converted to f32 separately.
+        assert_eq!(
+            Rational64::new(i64::MAX, 2).to_f32(),
+            Some(4611686018427387904f32)
+        );
+        assert_eq!(
+            Rational64::new(i64::MAX - 1, i64::MAX).to_f32(),
+            Some(1.0f32)
+        );
+        assert_eq!(
+            Rational64::new(3, i64::MAX).to_f32(),
+            Some(3.2526065e-19f32)
+        );
+
+        // This is just above the f32 midpoint 1 + 2^-24, so it rounds up. Its nearest f64 is
+        // the midpoint itself, which would round to even, i.e. down.
+        let above_half_ulp = Rational64::new((1 << 60) + (1 << 36) + 1, 1 << 60);
+        assert_eq!(above_half_ulp.to_f64(), Some(1.0 + 2f64.powi(-24)));
+        assert_eq!(above_half_ulp.to_f32(), Some(1.0 + f32::EPSILON));
+
+        assert_eq!(Ratio::<i32>::new_raw(1, 0).to_f32(), Some(f32::INFINITY));
+        assert_eq!(Ratio::<i32>::new_raw(0, 0).to_f32(), None);
+
+        #[cfg(feature = "num-bigint")]
+        {
+            let big = BigRational::from(BigInt::one() << 200);
+            assert_eq!(big.to_f32(), Some(f32::INFINITY));
+            assert_eq!(big.recip().to_f32(), Some(0.0));
+            // The smallest f32 subnormal, and half of it.
+            let tiny = BigRational::new(BigInt::one(), BigInt::one() << 149);
+            assert_eq!(tiny.to_f32(), Some(f32::from_bits(1)));
+            assert_eq!((tiny / BigInt::from(2)).to_f32(), Some(0.0));
+        }
+    }
+
+    #[test]
+    fn test_as_primitive() {
+        use num_traits::AsPrimitive;
+
+        fn mean<T: AsPrimitive<f64>>(xs: &[T]) -> f64 {
+            xs.iter().map(|x| x.as_()).sum::<f64>() / xs.len() as f64
+        }
+
+        assert_eq!(mean(&[_1_2, _3_2]), 1.0);
+        assert_eq!(mean(&[Ratio::new(1u8, 4)]), 0.25);
+        assert_eq!(AsPrimitive::<f64>::as_(_NEG1_2), -0.5);
+        assert_eq!(AsPrimitive::<f32>::as_(Ratio::new(1i32, 3)), 1.0f32 / 3.0);
+        assert_eq!(
+            AsPrimitive::<f32>::as_(Ratio::new(i128::MAX, 2)),
+            i128::MAX as f32 / 2.0
+        );
     }
 
     #[test]
     fn test_ldexp() {
-        use core::f64::{INFINITY, MAX_EXP, MIN_EXP, NAN, NEG_INFINITY};
+        const INFINITY: f64 = f64::INFINITY;
+        const MAX_EXP: i32 = f64::MAX_EXP;
+        const MIN_EXP: i32 = f64::MIN_EXP;
+        const NAN: f64 = f64::NAN;
+        const NEG_INFINITY: f64 = f64::NEG_INFINITY;
         assert_eq!(ldexp(1.0, 0), 1.0);
         assert_eq!(ldexp(1.0, 1), 2.0);
         assert_eq!(ldexp(0.0, 1), 0.0);
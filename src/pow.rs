@@ -2,7 +2,7 @@ use crate::Ratio;
 
 use core::cmp;
 use num_integer::Integer;
-use num_traits::{One, Pow};
+use num_traits::{checked_pow, CheckedMul, One, Pow, ToPrimitive};
 
 macro_rules! pow_unsigned_impl {
     (@ $exp:ty) => {
@@ -69,8 +69,8 @@ macro_rules! pow_signed_impl {
             match expon.cmp(&0) {
                 cmp::Ordering::Equal => One::one(),
                 cmp::Ordering::Less => {
-                    let expon = expon.wrapping_abs() as $unsigned;
-                    Pow::pow(self, expon).into_recip()
+                    // `unsigned_abs` gives the right magnitude for `MIN` too.
+                    Pow::pow(self, expon.unsigned_abs()).into_recip()
                 }
                 cmp::Ordering::Greater => Pow::pow(self, expon as $unsigned),
             }
@@ -112,6 +112,106 @@ pow_signed_impl!(i64, u64);
 pow_signed_impl!(i128, u128);
 pow_signed_impl!(isize, usize);
 
+/// Raises a value to a power, checking for overflow.
+///
+/// This complements the `Pow` trait, like `CheckedMul` complements `Mul`.
+pub trait CheckedPow<RHS>: Sized {
+    /// Returns `self` raised to the power `expon`, or `None` if that overflows or divides by
+    /// zero.
+    fn checked_pow(&self, expon: RHS) -> Option<Self>;
+}
+
+macro_rules! checked_pow_unsigned_impl {
+    ($($exp:ty)*) => {$(
+        impl<T: Clone + Integer + CheckedMul> CheckedPow<$exp> for Ratio<T> {
+            #[inline]
+            fn checked_pow(&self, expon: $exp) -> Option<Ratio<T>> {
+                let expon = expon.to_usize()?;
+                Some(Ratio::new_raw(
+                    checked_pow(self.numer.clone(), expon)?,
+                    checked_pow(self.denom.clone(), expon)?,
+                ))
+            }
+        }
+    )*};
+}
+checked_pow_unsigned_impl!(u8 u16 u32 u64 u128 usize);
+
+macro_rules! checked_pow_signed_impl {
+    ($($exp:ty)*) => {$(
+        impl<T: Clone + Integer + CheckedMul> CheckedPow<$exp> for Ratio<T> {
+            #[inline]
+            fn checked_pow(&self, expon: $exp) -> Option<Ratio<T>> {
+                let pow = self.checked_pow(expon.unsigned_abs())?;
+                if expon < 0 {
+                    checked_recip(pow)
+                } else {
+                    Some(pow)
+                }
+            }
+        }
+    )*};
+}
+checked_pow_signed_impl!(i8 i16 i32 i64 i128 isize);
+
+fn checked_recip<T: Clone + Integer + CheckedMul>(r: Ratio<T>) -> Option<Ratio<T>> {
+    match r.numer.cmp(&T::zero()) {
+        cmp::Ordering::Equal => None,
+        cmp::Ordering::Greater => Some(Ratio::new_raw(r.denom, r.numer)),
+        cmp::Ordering::Less => {
+            // 2's-complement MIN may overflow negation -- instead we can check multiplying -1.
+            let n1 = T::zero() - T::one();
+            Some(Ratio::new_raw(
+                r.denom.checked_mul(&n1)?,
+                r.numer.checked_mul(&n1)?,
+            ))
+        }
+    }
+}
+
+impl<T: Clone + Integer + CheckedMul> Ratio<T> {
+    /// Raises the `Ratio` to the power `expon` like `CheckedPow`, but on overflow reports how
+    /// far the square-and-multiply got.
+    ///
+    /// The bits of `|expon|` are processed from the lowest. `Err(i)` means the overflow happened
+    /// at bit `i`, squaring up to `self^(2^i)` or multiplying that in, while
+    /// `self^(|expon| mod 2^i)` still fit. If only the reciprocal for a negative `expon`
+    /// overflows, this returns the bit length of `|expon|`.
+    ///
+    /// **Panics if `self` is zero and `expon` is negative.**
+    pub fn pow_checked_detailed(&self, expon: i32) -> Result<Ratio<T>, u32> {
+        fn mul<T: Clone + Integer + CheckedMul>(a: &Ratio<T>, b: &Ratio<T>) -> Option<Ratio<T>> {
+            Some(Ratio::new_raw(
+                a.numer.checked_mul(&b.numer)?,
+                a.denom.checked_mul(&b.denom)?,
+            ))
+        }
+
+        let mut bits = expon.unsigned_abs();
+        let mut base = self.clone();
+        let mut acc = Ratio::one();
+        let mut step = 0;
+        while bits > 0 {
+            if step > 0 {
+                base = mul(&base, &base).ok_or(step)?;
+            }
+            if bits & 1 == 1 {
+                acc = mul(&acc, &base).ok_or(step)?;
+            }
+            bits >>= 1;
+            step += 1;
+        }
+
+        if expon >= 0 {
+            Ok(acc)
+        } else if acc.numer.is_zero() {
+            panic!("division by zero");
+        } else {
+            checked_recip(acc).ok_or(step)
+        }
+    }
+}
+
 #[cfg(feature = "num-bigint")]
 mod bigint {
     use super::*;